@@ -1,5 +1,7 @@
 //! Development tools for the game. This plugin is only enabled in dev builds.
 
+use std::collections::VecDeque;
+
 use bevy::{
     dev_tools::states::log_transitions, input::common_conditions::input_just_pressed, prelude::*,
 };
@@ -9,13 +11,16 @@ use bevy::{
 use crate::{
     game::{
         arena::ArenaMode,
+        ballistics::TrajectoryPreview,
         camera::{
             display_pan_orbit_camera_state, update_pan_orbit_camera, PanOrbitAction,
             PanOrbitSettings,
         },
+        shattering::{ShatterEntity, ShatterSettings},
         sword::dummies::debug_draw_dummy_slots,
     },
     screen::Screen,
+    spawn_camera,
 };
 
 pub(super) fn plugin(app: &mut App) {
@@ -32,9 +37,15 @@ pub(super) fn plugin(app: &mut App) {
             debug_draw_dummy_slots,
             // Debug camera controls
             display_pan_orbit_camera_state.run_if(input_just_pressed(KeyCode::KeyC)),
+            toggle_trajectory_preview.run_if(input_just_pressed(KeyCode::KeyT)),
+            // Benchmarks
+            start_shatter_benchmark.run_if(input_just_pressed(KeyCode::KeyB)),
+            run_shatter_benchmark.run_if(in_state(Screen::Playing)),
         ),
     );
-    app.add_systems(Startup, camera_keybindings);
+    app.init_resource::<ShatterBenchmark>();
+    // Commands are applied between ordered startup systems, so the camera exists by then
+    app.add_systems(Startup, camera_keybindings.after(spawn_camera));
 }
 
 fn camera_keybindings(mut pan_orbit_settings: Query<&mut PanOrbitSettings>) {
@@ -47,3 +58,101 @@ fn camera_keybindings(mut pan_orbit_settings: Query<&mut PanOrbitSettings>) {
     settings.zoom_key = Some(KeyCode::ShiftLeft);
     settings.scroll_action = Some(PanOrbitAction::Zoom);
 }
+
+fn toggle_trajectory_preview(mut preview: ResMut<TrajectoryPreview>) {
+    preview.enabled = !preview.enabled;
+}
+
+/// Number of objects shattered on the same frame by the benchmark
+const SHATTER_BENCHMARK_COUNT: usize = 10;
+/// Number of frames measured after the shatters
+const SHATTER_BENCHMARK_FRAMES: u32 = 60;
+
+/// Compares the frame times of synchronous and asynchronous shattering
+#[derive(Resource, Default)]
+struct ShatterBenchmark {
+    /// Remaining runs, as the value of [`ShatterSettings::asynchronous`]
+    pending_runs: VecDeque<bool>,
+    current_run: Option<ShatterBenchmarkRun>,
+    restore_asynchronous: bool,
+}
+
+struct ShatterBenchmarkRun {
+    asynchronous: bool,
+    frames_left: u32,
+    max_frame_time: f32,
+    total_frame_time: f32,
+}
+
+fn start_shatter_benchmark(
+    mut benchmark: ResMut<ShatterBenchmark>,
+    settings: Res<ShatterSettings>,
+) {
+    if !benchmark.pending_runs.is_empty() || benchmark.current_run.is_some() {
+        return;
+    }
+    benchmark.pending_runs = [false, true].into();
+    benchmark.restore_asynchronous = settings.asynchronous;
+}
+
+fn run_shatter_benchmark(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    mut benchmark: ResMut<ShatterBenchmark>,
+    mut settings: ResMut<ShatterSettings>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    if let Some(run) = benchmark.current_run.as_mut() {
+        let frame_time = time.delta_seconds() * 1000.;
+        run.max_frame_time = run.max_frame_time.max(frame_time);
+        run.total_frame_time += frame_time;
+        run.frames_left -= 1;
+        if run.frames_left > 0 {
+            return;
+        }
+        info!(
+            "Shatter benchmark ({} x {}): max frame time {:.2} ms, average {:.2} ms",
+            SHATTER_BENCHMARK_COUNT,
+            if run.asynchronous { "async" } else { "sync" },
+            run.max_frame_time,
+            run.total_frame_time / SHATTER_BENCHMARK_FRAMES as f32
+        );
+        benchmark.current_run = None;
+        if benchmark.pending_runs.is_empty() {
+            settings.asynchronous = benchmark.restore_asynchronous;
+        }
+        return;
+    }
+
+    let Some(asynchronous) = benchmark.pending_runs.pop_front() else {
+        return;
+    };
+    settings.asynchronous = asynchronous;
+
+    // Generated meshes, so that they are not prefractured
+    let mesh = meshes.add(Sphere::new(0.5).mesh().ico(4).unwrap());
+    let material = materials.add(Color::WHITE);
+    for i in 0..SHATTER_BENCHMARK_COUNT {
+        let position = Vec3::new(i as f32 - SHATTER_BENCHMARK_COUNT as f32 / 2., 4., 0.);
+        let entity = commands
+            .spawn(PbrBundle {
+                mesh: mesh.clone(),
+                material: material.clone(),
+                transform: Transform::from_translation(position),
+                ..default()
+            })
+            .id();
+        commands.trigger(ShatterEntity {
+            entity,
+            impact_point: position + Vec3::Z * 0.5,
+            impact_velocity: Vec3::NEG_Z,
+        });
+    }
+    benchmark.current_run = Some(ShatterBenchmarkRun {
+        asynchronous,
+        frames_left: SHATTER_BENCHMARK_FRAMES,
+        max_frame_time: 0.,
+        total_frame_time: 0.,
+    });
+}
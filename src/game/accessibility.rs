@@ -0,0 +1,32 @@
+//! Player settings adjusting the visual and audio feedback.
+
+use bevy::{
+    app::App,
+    prelude::{ReflectResource, Resource},
+    reflect::Reflect,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<AccessibilitySettings>();
+    app.init_resource::<AccessibilitySettings>();
+}
+
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct AccessibilitySettings {
+    /// Camera shake on impacts
+    pub screen_shake: bool,
+    /// Frozen frames on hits
+    pub hit_stop: bool,
+    /// Audio ping panned towards the incoming threats in shield mode
+    pub threat_ping: bool,
+}
+impl Default for AccessibilitySettings {
+    fn default() -> Self {
+        Self {
+            screen_shake: true,
+            hit_stop: true,
+            threat_ping: false,
+        }
+    }
+}
@@ -1,7 +1,19 @@
-use bevy::{gltf::GltfMesh, prelude::*, utils::HashMap};
+use bevy::{
+    gltf::GltfMesh,
+    prelude::*,
+    tasks::{block_on, futures_lite::future},
+    utils::HashMap,
+};
 use bevy_rapier3d::prelude::{Collider, ComputedColliderShape};
 
-use super::spawn::{dummy::DummyCachedData, jug::JugCachedData, shield::ShieldCachedData};
+use super::{
+    shattering::{FractureLibraries, FractureVariantShards, FractureVariantTask},
+    spawn::{
+        dummy::{DummyCachedData, DUMMY_ARCHETYPES},
+        jug::JugCachedData,
+        shield::ShieldCachedData,
+    },
+};
 
 pub const ASSETS_SCALE: f32 = 1.;
 pub const ARENA_SCALE: f32 = 0.75;
@@ -318,6 +330,7 @@ pub struct AssetsProcessing {
     pub dummy: bool,
     pub jugs: bool,
     pub shield: bool,
+    pub fracture_libraries: bool,
 }
 
 pub fn process_dummy_asset(
@@ -326,8 +339,12 @@ pub fn process_dummy_asset(
     assets_gltf: Res<Assets<Gltf>>,
     assets_gltfmesh: Res<Assets<GltfMesh>>,
     meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
     mut assets_processing: ResMut<AssetsProcessing>,
 ) {
+    if assets_processing.dummy {
+        return;
+    }
     let gltf_handle = &gltf_handles[&GltfKey::Dummy];
     let Some(gltf) = assets_gltf.get(gltf_handle) else {
         return;
@@ -339,11 +356,33 @@ pub fn process_dummy_asset(
     let Some(mesh) = meshes.get(mesh_handle) else {
         return;
     };
+    let Some(base_material) = materials.get(&gltf.materials[0]).cloned() else {
+        return;
+    };
 
     let Some(collider) = Collider::from_bevy_mesh(mesh, &ComputedColliderShape::ConvexHull) else {
         return;
     };
-    commands.insert_resource(DummyCachedData { collider });
+    let mut kind_materials = HashMap::new();
+    for archetype in DUMMY_ARCHETYPES.iter() {
+        let base_color = base_material.base_color.to_linear();
+        let tint = archetype.tint.to_linear();
+        let material = materials.add(StandardMaterial {
+            base_color: LinearRgba::new(
+                base_color.red * tint.red,
+                base_color.green * tint.green,
+                base_color.blue * tint.blue,
+                base_color.alpha,
+            )
+            .into(),
+            ..base_material.clone()
+        });
+        kind_materials.insert(archetype.kind, material);
+    }
+    commands.insert_resource(DummyCachedData {
+        collider,
+        materials: kind_materials,
+    });
     assets_processing.dummy = true;
 }
 
@@ -355,6 +394,9 @@ pub fn process_jug_asset(
     meshes: ResMut<Assets<Mesh>>,
     mut assets_processing: ResMut<AssetsProcessing>,
 ) {
+    if assets_processing.jugs {
+        return;
+    }
     let gltf_handle = &gltf_handles[&GltfKey::Jug1];
     let Some(gltf) = assets_gltf.get(gltf_handle) else {
         return;
@@ -401,6 +443,69 @@ pub fn process_shield_asset(
     assets_processing.shield = true;
 }
 
+/// Fracture variants being computed on background threads
+#[derive(Default)]
+pub struct PendingFractureLibraries {
+    started: bool,
+    tasks: Vec<(AssetId<Mesh>, FractureVariantTask)>,
+    variants: HashMap<AssetId<Mesh>, Vec<FractureVariantShards>>,
+}
+
+/// Precomputes the fracture variants of the breakable meshes, without blocking the loading screen
+pub fn process_fracture_libraries(
+    gltf_handles: Res<HandleMap<GltfKey>>,
+    assets_gltf: Res<Assets<Gltf>>,
+    assets_gltfmesh: Res<Assets<GltfMesh>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut fracture_libraries: ResMut<FractureLibraries>,
+    mut assets_processing: ResMut<AssetsProcessing>,
+    mut pending: Local<PendingFractureLibraries>,
+) {
+    if assets_processing.fracture_libraries {
+        return;
+    }
+    if !pending.started {
+        let mut mesh_handles = Vec::new();
+        for key in [GltfKey::Jug1, GltfKey::Dummy] {
+            let Some(gltf) = assets_gltf.get(&gltf_handles[&key]) else {
+                return;
+            };
+            let Some(gltf_mesh) = assets_gltfmesh.get(&gltf.meshes[0]) else {
+                return;
+            };
+            mesh_handles.push(gltf_mesh.primitives[0].mesh.clone());
+        }
+        if !mesh_handles.iter().all(|handle| meshes.contains(handle)) {
+            return;
+        }
+        for mesh_handle in mesh_handles.iter() {
+            let mesh = meshes.get(mesh_handle).unwrap();
+            pending.variants.insert(mesh_handle.id(), Vec::new());
+            for task in FractureLibraries::spawn_variant_tasks(mesh) {
+                pending.tasks.push((mesh_handle.id(), task));
+            }
+        }
+        pending.started = true;
+    }
+
+    let mut running_tasks = Vec::new();
+    for (mesh_id, mut task) in std::mem::take(&mut pending.tasks) {
+        match block_on(future::poll_once(&mut task)) {
+            Some(Some(variant)) => pending.variants.entry(mesh_id).or_default().push(variant),
+            Some(None) => (),
+            None => running_tasks.push((mesh_id, task)),
+        }
+    }
+    pending.tasks = running_tasks;
+    if !pending.tasks.is_empty() {
+        return;
+    }
+    for (mesh_id, variants) in std::mem::take(&mut pending.variants) {
+        fracture_libraries.insert_library(mesh_id, variants, &mut meshes);
+    }
+    assets_processing.fracture_libraries = true;
+}
+
 pub fn all_assets_loaded(
     asset_server: Res<AssetServer>,
     image_handles: Res<HandleMap<ImageKey>>,
@@ -422,5 +527,8 @@ pub fn all_assets_loaded(
 
 // Quick &  dirty
 pub fn all_assets_processed(assets_processing: Res<AssetsProcessing>) -> bool {
-    assets_processing.dummy && assets_processing.jugs && assets_processing.shield
+    assets_processing.dummy
+        && assets_processing.jugs
+        && assets_processing.shield
+        && assets_processing.fracture_libraries
 }
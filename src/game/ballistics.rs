@@ -0,0 +1,91 @@
+//! Ballistic trajectories for thrown objects.
+
+use bevy::{
+    app::{App, Update},
+    color::{palettes::css::ORANGE, Alpha, Color},
+    math::Vec3,
+    prelude::{Gizmos, Query, Res, Resource, Transform, With},
+    reflect::Reflect,
+};
+use bevy_rapier3d::prelude::{RapierConfiguration, Velocity};
+
+use super::spawn::jug::Jug;
+
+/// Number of points used to draw a predicted trajectory
+pub const TRAJECTORY_PREVIEW_SEGMENTS: usize = 24;
+/// Duration of the trajectory drawn ahead of a thrown object
+pub const TRAJECTORY_PREVIEW_DURATION_S: f32 = 1.2;
+pub const TRAJECTORY_PREVIEW_COLOR: Color = Color::Srgba(ORANGE);
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<TrajectoryPreview>();
+    app.init_resource::<TrajectoryPreview>();
+
+    app.add_systems(Update, draw_jugs_trajectories);
+}
+
+/// Toggles the drawing of the predicted trajectory of the jugs in flight. Can serve as a telegraph.
+#[derive(Resource, Debug, Default, Reflect)]
+pub struct TrajectoryPreview {
+    pub enabled: bool,
+}
+
+/// Initial velocity needed to go from `from` to `to` in exactly `flight_time` seconds under `gravity`
+pub fn ballistic_launch_velocity(from: Vec3, to: Vec3, flight_time: f32, gravity: Vec3) -> Vec3 {
+    (to - from - 0.5 * gravity * flight_time * flight_time) / flight_time
+}
+
+/// Position after `time` seconds of a ballistic flight
+pub fn ballistic_position(origin: Vec3, velocity: Vec3, gravity: Vec3, time: f32) -> Vec3 {
+    origin + velocity * time + 0.5 * gravity * time * time
+}
+
+/// Time and distance of the closest approach of a ballistic flight to `target`, measured on the horizontal plane.
+///
+/// `None` when the flight moves away from the target.
+pub fn closest_approach(
+    origin: Vec3,
+    velocity: Vec3,
+    gravity: Vec3,
+    target: Vec3,
+) -> Option<(f32, f32)> {
+    let horizontal_velocity = velocity.with_y(0.);
+    let speed_squared = horizontal_velocity.length_squared();
+    if speed_squared <= f32::EPSILON {
+        return None;
+    }
+    let time = (target - origin).with_y(0.).dot(horizontal_velocity) / speed_squared;
+    if time < 0. {
+        return None;
+    }
+    let distance = ballistic_position(origin, velocity, gravity, time).distance(target);
+    Some((time, distance))
+}
+
+/// Where a target moving at a constant `target_velocity` will be in `flight_time` seconds
+pub fn lead_target(target: Vec3, target_velocity: Vec3, flight_time: f32) -> Vec3 {
+    target + target_velocity * flight_time
+}
+
+fn draw_jugs_trajectories(
+    mut gizmos: Gizmos,
+    preview: Res<TrajectoryPreview>,
+    rapier_config: Res<RapierConfiguration>,
+    jugs_query: Query<(&Transform, &Velocity), With<Jug>>,
+) {
+    if !preview.enabled {
+        return;
+    }
+    for (transform, velocity) in jugs_query.iter() {
+        let points = (0..=TRAJECTORY_PREVIEW_SEGMENTS).map(|i| {
+            let t = i as f32 / TRAJECTORY_PREVIEW_SEGMENTS as f32 * TRAJECTORY_PREVIEW_DURATION_S;
+            ballistic_position(
+                transform.translation,
+                velocity.linvel,
+                rapier_config.gravity,
+                t,
+            )
+        });
+        gizmos.linestrip(points, TRAJECTORY_PREVIEW_COLOR.with_alpha(0.8));
+    }
+}
@@ -0,0 +1,184 @@
+//! Entities breaking under the contact forces computed by the physics engine, or the damage dealt by the gameplay.
+
+use bevy::{
+    app::{App, Update},
+    asset::Handle,
+    math::Vec3,
+    pbr::StandardMaterial,
+    prelude::{
+        Added, Commands, Component, Entity, Event, EventReader, IntoSystemConfigs, Query, Res,
+        Transform, Trigger,
+    },
+    reflect::Reflect,
+    time::Time,
+};
+use bevy_rapier3d::prelude::{
+    ActiveEvents, ContactForceEvent, ContactForceEventThreshold, Velocity,
+};
+
+use crate::AppSet;
+
+use super::shattering::{ShatterEntity, SHARDS_DESPAWN_DELAY_MS};
+
+/// Damage dealt per unit of contact force and per second
+pub const CONTACT_FORCE_DAMAGE_FACTOR: f32 = 1.;
+/// Breaks any [`Breakable`] at once
+pub const BREAK_DAMAGE: f32 = f32::INFINITY;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Breakable>();
+    app.register_type::<FractureMode>();
+    app.register_type::<DamageBreakable>();
+
+    app.add_systems(
+        Update,
+        (
+            enable_contact_force_events,
+            break_on_contact_forces.in_set(AppSet::Update),
+        ),
+    );
+
+    app.observe(damage_breakable);
+}
+
+/// How a [`Breakable`] comes apart
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+pub enum FractureMode {
+    /// Cut in two halves along the impact direction
+    Slice,
+    /// Broken into many shards
+    #[default]
+    Shatter,
+}
+
+/// Breaks when the contacts forces and the [`DamageBreakable`] applied to it deal enough damage
+#[derive(Component, Debug, Clone, Reflect)]
+pub struct Breakable {
+    /// Damage the entity can take before breaking
+    pub health: f32,
+    /// Contact forces below this are ignored, so that the entity can rest on the ground
+    pub min_force: f32,
+    pub mode: FractureMode,
+    /// Material of the fragments, uses the material of the entity if not set
+    pub material_override: Option<Handle<StandardMaterial>>,
+    /// Number of random cuts when shattering. Uses a precomputed fracture pattern if available when not set.
+    pub shard_count: Option<u32>,
+    pub debris_lifetime_ms: u64,
+}
+impl Default for Breakable {
+    fn default() -> Self {
+        Self {
+            health: 1.,
+            min_force: 0.,
+            mode: FractureMode::default(),
+            material_override: None,
+            shard_count: None,
+            debris_lifetime_ms: SHARDS_DESPAWN_DELAY_MS,
+        }
+    }
+}
+
+impl Breakable {
+    /// Ignores the contact forces, only broken by [`DamageBreakable`]
+    pub fn damage_only() -> Self {
+        Self {
+            min_force: f32::INFINITY,
+            ..Default::default()
+        }
+    }
+}
+
+/// Deals damage to a [`Breakable`], shattering it when its health runs out
+#[derive(Event, Debug, Clone, Copy, Reflect)]
+pub struct DamageBreakable {
+    pub entity: Entity,
+    pub damage: f32,
+    pub impact_point: Vec3,
+    pub impact_velocity: Vec3,
+}
+
+fn enable_contact_force_events(
+    mut commands: Commands,
+    mut breakables: Query<(Entity, &Breakable, Option<&mut ActiveEvents>), Added<Breakable>>,
+) {
+    for (entity, breakable, active_events) in breakables.iter_mut() {
+        if !breakable.min_force.is_finite() {
+            continue;
+        }
+        match active_events {
+            Some(mut active_events) => *active_events |= ActiveEvents::CONTACT_FORCE_EVENTS,
+            None => {
+                commands
+                    .entity(entity)
+                    .insert(ActiveEvents::CONTACT_FORCE_EVENTS);
+            }
+        }
+        commands
+            .entity(entity)
+            .insert(ContactForceEventThreshold(breakable.min_force));
+    }
+}
+
+fn break_on_contact_forces(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut contact_force_events: EventReader<ContactForceEvent>,
+    breakables: Query<(&Breakable, &Transform, Option<&Velocity>)>,
+    others: Query<(&Transform, Option<&Velocity>)>,
+) {
+    for event in contact_force_events.read() {
+        for (entity, other) in [
+            (event.collider1, event.collider2),
+            (event.collider2, event.collider1),
+        ] {
+            let Ok((breakable, transform, velocity)) = breakables.get(entity) else {
+                continue;
+            };
+            if event.total_force_magnitude < breakable.min_force {
+                continue;
+            }
+
+            let (impact_point, impact_velocity) = match others.get(other) {
+                Ok((other_transform, other_velocity)) => (
+                    // Contact points are not reported with the forces, the middle of the two bodies is close enough
+                    (transform.translation + other_transform.translation) / 2.,
+                    other_velocity.map_or(Vec3::ZERO, |v| v.linvel)
+                        - velocity.map_or(Vec3::ZERO, |v| v.linvel),
+                ),
+                Err(_) => (transform.translation, Vec3::ZERO),
+            };
+            commands.trigger(DamageBreakable {
+                entity,
+                damage: event.total_force_magnitude
+                    * CONTACT_FORCE_DAMAGE_FACTOR
+                    * time.delta_seconds(),
+                impact_point,
+                impact_velocity,
+            });
+        }
+    }
+}
+
+fn damage_breakable(
+    trigger: Trigger<DamageBreakable>,
+    mut commands: Commands,
+    mut breakables: Query<&mut Breakable>,
+) {
+    let damage = trigger.event();
+    let Ok(mut breakable) = breakables.get_mut(damage.entity) else {
+        return;
+    };
+    // Already broken, waiting for its shatter
+    if breakable.health <= 0. {
+        return;
+    }
+    breakable.health -= damage.damage;
+    if breakable.health > 0. {
+        return;
+    }
+    commands.trigger(ShatterEntity {
+        entity: damage.entity,
+        impact_point: damage.impact_point,
+        impact_velocity: damage.impact_velocity,
+    });
+}
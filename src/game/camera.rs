@@ -10,6 +10,10 @@ use bevy::{
     prelude::{Bundle, Camera3dBundle, Component, DetectChanges, EventReader, KeyCode, Query, Res},
     transform::components::Transform,
 };
+use bevy_rapier3d::prelude::{QueryFilter, RapierContext};
+
+/// Distance kept between the camera and the geometry blocking its view
+pub const CAMERA_COLLISION_MARGIN: f32 = 0.5;
 
 // Updated & modified version of the "Pan-orbit-camera" from the Bevy Cheat Book (https://bevy-cheatbook.github.io/cookbook/pan-orbit-camera.html).
 
@@ -68,6 +72,16 @@ pub struct PanOrbitSettings {
 
     pub auto_orbit: bool,
     pub auto_orbit_factor: f32,
+
+    /// Min and max pitch, in radians
+    pub pitch_limits: Option<(f32, f32)>,
+    /// Min and max distance to the center
+    pub radius_limits: Option<(f32, f32)>,
+    /// The camera never goes below this height
+    pub min_height: Option<f32>,
+    /// Pulls the camera in when fixed colliders block the view of the center.
+    /// Casts from the center, raised to `min_height` so that a center below the ground still works.
+    pub avoid_collisions: bool,
 }
 impl Default for PanOrbitSettings {
     fn default() -> Self {
@@ -83,6 +97,11 @@ impl Default for PanOrbitSettings {
             scroll_pixel_sensitivity: 1.0,
             auto_orbit: true,
             auto_orbit_factor: 0.001,
+            // Looking at the arena from above, never from below the ground
+            pitch_limits: Some((-1.4, 0.1)),
+            radius_limits: Some((2., 120.)),
+            min_height: Some(0.5),
+            avoid_collisions: false,
         }
     }
 }
@@ -118,6 +137,7 @@ pub fn update_pan_orbit_camera(
     kbd: Res<ButtonInput<KeyCode>>,
     mut evr_motion: EventReader<MouseMotion>,
     mut evr_scroll: EventReader<MouseWheel>,
+    rapier_context: Res<RapierContext>,
     mut q_camera: Query<(&PanOrbitSettings, &mut PanOrbitState, &mut Transform)>,
 ) {
     // First, accumulate the total amount of
@@ -266,6 +286,20 @@ pub fn update_pan_orbit_camera(
             }
         }
 
+        // Keep the state within the limits, whoever changed it
+        if let Some((min_radius, max_radius)) = settings.radius_limits {
+            if !(min_radius..=max_radius).contains(&state.radius) {
+                state.radius = state.radius.clamp(min_radius, max_radius);
+                any_change = true;
+            }
+        }
+        if let Some((min_pitch, max_pitch)) = settings.pitch_limits {
+            if !(min_pitch..=max_pitch).contains(&state.pitch) {
+                state.pitch = state.pitch.clamp(min_pitch, max_pitch);
+                any_change = true;
+            }
+        }
+
         // Finally, compute the new camera transform.
         // (if we changed anything, or if the pan-orbit
         // controller was just added and thus we are running
@@ -277,6 +311,35 @@ pub fn update_pan_orbit_camera(
             // To position the camera, get the backward direction vector
             // and place the camera at the desired radius from the center.
             transform.translation = state.center + transform.back() * state.radius;
+
+            // Constraints on the transform only, the state is left as is
+            if settings.avoid_collisions {
+                let mut ray_origin = state.center;
+                if let Some(min_height) = settings.min_height {
+                    ray_origin.y = ray_origin.y.max(min_height);
+                }
+                let to_camera = transform.translation - ray_origin;
+                let distance = to_camera.length();
+                if distance > CAMERA_COLLISION_MARGIN {
+                    let direction = to_camera / distance;
+                    if let Some((_entity, toi)) = rapier_context.cast_ray(
+                        ray_origin,
+                        direction,
+                        distance,
+                        true,
+                        QueryFilter::only_fixed(),
+                    ) {
+                        transform.translation =
+                            ray_origin + direction * (toi - CAMERA_COLLISION_MARGIN).max(0.);
+                    }
+                }
+            }
+            if let Some(min_height) = settings.min_height {
+                if transform.translation.y < min_height {
+                    transform.translation.y = min_height;
+                    transform.look_at(state.center, Vec3::Y);
+                }
+            }
         }
     }
 }
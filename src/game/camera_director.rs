@@ -0,0 +1,427 @@
+//! Moves the pan-orbit camera between named presets, and layers short effects on top of it.
+//!
+//! The effects (trauma shake, push-ins) offset the transform computed from the [`PanOrbitState`] and never write into it.
+//!
+//! The director runs on the real time so that slow-motions do not drag the camera along.
+
+use std::{
+    f32::consts::{PI, TAU},
+    time::Duration,
+};
+
+use bevy::{
+    app::{App, Update},
+    math::{EulerRot, Quat, Vec3},
+    prelude::{
+        Camera, Commands, Component, Entity, Event, IntoSystemConfigs, OnEnter, Query, Res,
+        Transform, Trigger, With,
+    },
+    reflect::Reflect,
+    time::{Real, Time, Timer, TimerMode},
+};
+
+use super::{
+    accessibility::AccessibilitySettings,
+    arena::ArenaMode,
+    camera::{update_pan_orbit_camera, PanOrbitSettings, PanOrbitState},
+    shattering::ShatterEntity,
+    sword::slicing::MultiSliceEvent,
+};
+
+/// Trauma added per unit of impact speed
+pub const IMPACT_TRAUMA_FACTOR: f32 = 0.03;
+pub const IMPACT_MAX_TRAUMA: f32 = 0.6;
+/// Trauma lost per second, the shake fades out as trauma goes back to 0
+pub const CAMERA_TRAUMA_DECAY_PER_SEC: f32 = 1.5;
+/// Offset of the camera at full trauma, in world units
+pub const CAMERA_SHAKE_MAX_OFFSET: f32 = 0.5;
+/// Rotation of the camera at full trauma, in radians
+pub const CAMERA_SHAKE_MAX_ANGLE: f32 = 0.05;
+pub const CAMERA_SHAKE_FREQUENCY: f32 = 17.;
+/// Fraction of the radius the camera moves in for each target after the first one of a multi-slice
+pub const COMBO_PUSH_IN_PER_TARGET: f32 = 0.06;
+pub const COMBO_PUSH_IN_MAX: f32 = 0.25;
+pub const COMBO_PUSH_IN_DURATION_MS: u64 = 1600;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<CameraPresetKey>();
+
+    app.add_systems(OnEnter(ArenaMode::GameOver), set_game_over_camera);
+    app.add_systems(
+        Update,
+        (
+            (update_camera_transitions, refresh_camera_effects).before(update_pan_orbit_camera),
+            apply_camera_effects.after(update_pan_orbit_camera),
+        ),
+    );
+
+    app.observe(start_camera_transition);
+    app.observe(shake_on_impacts);
+    app.observe(push_in_on_combos);
+    app.observe(add_camera_trauma);
+    app.observe(start_camera_push_in);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum CameraPresetKey {
+    Title,
+    Sword,
+    Shield,
+    GameOver,
+}
+
+impl CameraPresetKey {
+    pub fn preset(&self) -> &'static CameraPreset {
+        CAMERA_PRESETS
+            .iter()
+            .find(|preset| preset.key == *self)
+            .unwrap()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CameraEase {
+    SmoothStep,
+    CubicInOut,
+    QuadOut,
+}
+
+impl CameraEase {
+    pub fn sample(&self, t: f32) -> f32 {
+        let t = t.clamp(0., 1.);
+        match self {
+            CameraEase::SmoothStep => t * t * (3. - 2. * t),
+            CameraEase::CubicInOut => {
+                if t < 0.5 {
+                    4. * t * t * t
+                } else {
+                    1. - (-2. * t + 2.).powi(3) / 2.
+                }
+            }
+            CameraEase::QuadOut => 1. - (1. - t) * (1. - t),
+        }
+    }
+}
+
+/// The fields of a [`PanOrbitState`] that can be tweened
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CameraPose {
+    pub center: Vec3,
+    pub radius: f32,
+    pub pitch: f32,
+    pub yaw: f32,
+}
+
+impl CameraPose {
+    fn from_state(state: &PanOrbitState) -> Self {
+        Self {
+            center: state.center,
+            radius: state.radius,
+            pitch: state.pitch,
+            yaw: state.yaw,
+        }
+    }
+
+    /// Angles go the shortest way around
+    fn lerp(&self, to: &CameraPose, t: f32) -> Self {
+        let lerp_angle = |from: f32, to: f32| {
+            let delta = (to - from + PI).rem_euclid(TAU) - PI;
+            from + delta * t
+        };
+        Self {
+            center: self.center.lerp(to.center, t),
+            radius: self.radius + (to.radius - self.radius) * t,
+            pitch: lerp_angle(self.pitch, to.pitch),
+            yaw: lerp_angle(self.yaw, to.yaw),
+        }
+    }
+
+    fn apply(&self, state: &mut PanOrbitState) {
+        state.center = self.center;
+        state.radius = self.radius;
+        state.pitch = self.pitch;
+        state.yaw = self.yaw;
+        state.needs_transform_refresh = true;
+    }
+}
+
+pub struct CameraPreset {
+    pub key: CameraPresetKey,
+    pub pose: CameraPose,
+    /// Auto orbit speed once the camera reached the preset
+    pub auto_orbit: Option<f32>,
+    pub transition_ms: u64,
+    pub ease: CameraEase,
+    /// How far the player can move the camera away from the preset
+    pub player_control: Option<PlayerCameraLimits>,
+}
+
+pub struct PlayerCameraLimits {
+    /// Max yaw away from the preset, on each side, in radians
+    pub max_yaw_offset: f32,
+    /// Min and max factor applied on the preset radius
+    pub zoom_range: (f32, f32),
+}
+
+pub const CAMERA_PRESETS: [CameraPreset; 4] = [
+    CameraPreset {
+        key: CameraPresetKey::Title,
+        pose: CameraPose {
+            center: Vec3::ZERO,
+            radius: 51.71328,
+            pitch: -0.3002041,
+            yaw: 0.5580911,
+        },
+        auto_orbit: Some(0.001),
+        transition_ms: 1500,
+        ease: CameraEase::SmoothStep,
+        player_control: None,
+    },
+    CameraPreset {
+        key: CameraPresetKey::Sword,
+        pose: CameraPose {
+            center: Vec3::new(-0.18647689, 4.443227, 1.4876647),
+            radius: 19.983498,
+            pitch: -0.3036932,
+            yaw: 3.1415827,
+        },
+        auto_orbit: None,
+        transition_ms: 1200,
+        ease: CameraEase::CubicInOut,
+        player_control: Some(PlayerCameraLimits {
+            max_yaw_offset: 0.6,
+            zoom_range: (0.7, 1.3),
+        }),
+    },
+    CameraPreset {
+        key: CameraPresetKey::Shield,
+        pose: CameraPose {
+            center: Vec3::new(0.38106346, -4.2590923, -3.7643096),
+            radius: 60.033787,
+            pitch: -0.6771932,
+            yaw: -3.124136,
+        },
+        auto_orbit: None,
+        transition_ms: 1200,
+        ease: CameraEase::CubicInOut,
+        player_control: Some(PlayerCameraLimits {
+            max_yaw_offset: 0.8,
+            zoom_range: (0.75, 1.2),
+        }),
+    },
+    CameraPreset {
+        key: CameraPresetKey::GameOver,
+        pose: CameraPose {
+            center: Vec3::new(0., 2., 0.),
+            radius: 32.,
+            pitch: -0.45,
+            yaw: 3.1415827,
+        },
+        auto_orbit: Some(0.004),
+        transition_ms: 2500,
+        ease: CameraEase::QuadOut,
+        player_control: None,
+    },
+];
+
+/// Tweens the camera to a preset
+#[derive(Event, Debug)]
+pub struct SetCameraPreset(pub CameraPresetKey);
+
+/// Adds trauma to the camera, from 0 to 1. The shake grows with the square of the trauma.
+#[derive(Event, Debug)]
+pub struct AddCameraTrauma(pub f32);
+
+/// Moves the camera in towards its center, then back
+#[derive(Event, Debug)]
+pub struct PushInCamera {
+    /// Fraction of the radius
+    pub amount: f32,
+    pub duration: Duration,
+}
+
+/// Preset the camera is moving to or resting on
+#[derive(Component, Debug)]
+pub struct CurrentCameraPreset(pub CameraPresetKey);
+
+/// The camera is moving to a preset
+#[derive(Component, Debug)]
+pub struct CameraTransition {
+    from: CameraPose,
+    to: CameraPose,
+    ease: CameraEase,
+    auto_orbit: Option<f32>,
+    timer: Timer,
+}
+
+#[derive(Component, Debug, Default)]
+struct CameraEffects {
+    trauma: f32,
+    /// Drives the shake noise
+    shake_time: f32,
+    push_in: Option<(f32, Timer)>,
+    /// The transform still holds offsets from the previous frame
+    dirty: bool,
+}
+
+fn start_camera_transition(
+    trigger: Trigger<SetCameraPreset>,
+    mut commands: Commands,
+    mut camera_query: Query<(Entity, &PanOrbitState, &mut PanOrbitSettings)>,
+) {
+    let Ok((camera, cam_state, mut cam_settings)) = camera_query.get_single_mut() else {
+        return;
+    };
+    let preset = trigger.event().0.preset();
+    cam_settings.auto_orbit = false;
+    commands.entity(camera).insert((
+        CurrentCameraPreset(preset.key),
+        CameraTransition {
+            from: CameraPose::from_state(cam_state),
+            to: preset.pose,
+            ease: preset.ease,
+            auto_orbit: preset.auto_orbit,
+            timer: Timer::new(Duration::from_millis(preset.transition_ms), TimerMode::Once),
+        },
+    ));
+}
+
+fn update_camera_transitions(
+    mut commands: Commands,
+    real_time: Res<Time<Real>>,
+    mut camera_query: Query<(
+        Entity,
+        &mut CameraTransition,
+        &mut PanOrbitState,
+        &mut PanOrbitSettings,
+    )>,
+) {
+    for (camera, mut transition, mut cam_state, mut cam_settings) in camera_query.iter_mut() {
+        transition.timer.tick(real_time.delta());
+        let t = transition.ease.sample(transition.timer.fraction());
+        transition
+            .from
+            .lerp(&transition.to, t)
+            .apply(&mut cam_state);
+        if transition.timer.finished() {
+            if let Some(auto_orbit_factor) = transition.auto_orbit {
+                cam_settings.auto_orbit = true;
+                cam_settings.auto_orbit_factor = auto_orbit_factor;
+            }
+            commands.entity(camera).remove::<CameraTransition>();
+        }
+    }
+}
+
+fn set_game_over_camera(mut commands: Commands) {
+    commands.trigger(SetCameraPreset(CameraPresetKey::GameOver));
+}
+
+fn shake_on_impacts(trigger: Trigger<ShatterEntity>, mut commands: Commands) {
+    let trauma =
+        (trigger.event().impact_velocity.length() * IMPACT_TRAUMA_FACTOR).min(IMPACT_MAX_TRAUMA);
+    if trauma > 0. {
+        commands.trigger(AddCameraTrauma(trauma));
+    }
+}
+
+fn push_in_on_combos(trigger: Trigger<MultiSliceEvent>, mut commands: Commands) {
+    commands.trigger(PushInCamera {
+        amount: (COMBO_PUSH_IN_PER_TARGET * trigger.event().count.saturating_sub(1) as f32)
+            .min(COMBO_PUSH_IN_MAX),
+        duration: Duration::from_millis(COMBO_PUSH_IN_DURATION_MS),
+    });
+}
+
+fn add_camera_trauma(
+    trigger: Trigger<AddCameraTrauma>,
+    accessibility: Res<AccessibilitySettings>,
+    mut camera_query: Query<&mut CameraEffects>,
+) {
+    if !accessibility.screen_shake {
+        return;
+    }
+    for mut effects in camera_query.iter_mut() {
+        effects.trauma = (effects.trauma + trigger.event().0).min(1.);
+    }
+}
+
+fn start_camera_push_in(
+    trigger: Trigger<PushInCamera>,
+    mut camera_query: Query<&mut CameraEffects>,
+) {
+    let push_in = trigger.event();
+    for mut effects in camera_query.iter_mut() {
+        effects.push_in = Some((
+            push_in.amount,
+            Timer::new(push_in.duration, TimerMode::Once),
+        ));
+    }
+}
+
+/// Ticks the effects and asks for a clean transform while they are visible
+fn refresh_camera_effects(
+    mut commands: Commands,
+    real_time: Res<Time<Real>>,
+    mut camera_query: Query<(Entity, Option<&mut CameraEffects>, &mut PanOrbitState), With<Camera>>,
+) {
+    for (camera, effects, mut cam_state) in camera_query.iter_mut() {
+        let Some(mut effects) = effects else {
+            commands.entity(camera).insert(CameraEffects::default());
+            continue;
+        };
+        effects.trauma =
+            (effects.trauma - CAMERA_TRAUMA_DECAY_PER_SEC * real_time.delta_seconds()).max(0.);
+        effects.shake_time += real_time.delta_seconds();
+        if let Some((_, timer)) = effects.push_in.as_mut() {
+            timer.tick(real_time.delta());
+        }
+        if effects
+            .push_in
+            .as_ref()
+            .is_some_and(|(_, timer)| timer.finished())
+        {
+            effects.push_in = None;
+        }
+        if effects.dirty {
+            cam_state.needs_transform_refresh = true;
+        }
+    }
+}
+
+/// Smooth noise in [-1, 1], different for each seed
+fn shake_noise(seed: f32, time: f32) -> f32 {
+    let t = time * CAMERA_SHAKE_FREQUENCY;
+    ((t + seed * 13.7).sin() + (1.83 * t + seed * 5.3).sin()) / 2.
+}
+
+fn apply_camera_effects(
+    mut camera_query: Query<(&mut CameraEffects, &PanOrbitState, &mut Transform)>,
+) {
+    for (mut effects, cam_state, mut transform) in camera_query.iter_mut() {
+        let mut offset = Vec3::ZERO;
+        if let Some((amount, timer)) = &effects.push_in {
+            // In then out
+            let profile = (PI * timer.fraction()).sin();
+            offset += transform.forward() * cam_state.radius * amount * profile;
+        }
+        let shake = effects.trauma * effects.trauma;
+        let mut rotation = Quat::IDENTITY;
+        if shake > 0. {
+            let time = effects.shake_time;
+            offset += CAMERA_SHAKE_MAX_OFFSET
+                * shake
+                * (transform.right() * shake_noise(0., time)
+                    + transform.up() * shake_noise(1., time));
+            rotation = Quat::from_euler(
+                EulerRot::YXZ,
+                CAMERA_SHAKE_MAX_ANGLE * shake * shake_noise(2., time),
+                CAMERA_SHAKE_MAX_ANGLE * shake * shake_noise(3., time),
+                CAMERA_SHAKE_MAX_ANGLE * shake * shake_noise(4., time),
+            );
+        }
+        effects.dirty = offset != Vec3::ZERO || rotation != Quat::IDENTITY;
+        transform.translation += offset;
+        transform.rotation *= rotation;
+    }
+}
@@ -7,8 +7,8 @@ use crate::{
 use bevy::{
     app::{App, Update},
     prelude::{
-        in_state, BuildChildren, Commands, Component, ImageBundle, IntoSystemConfigs, NextState,
-        OnEnter, Query, Res, ResMut, Resource, StateScoped, With,
+        in_state, not, resource_exists, BuildChildren, Commands, Component, ImageBundle,
+        IntoSystemConfigs, NextState, OnEnter, Query, Res, ResMut, Resource, StateScoped, With,
     },
     reflect::Reflect,
     text::Text,
@@ -21,7 +21,9 @@ use crate::screen::Screen;
 use super::{
     arena::ArenaMode,
     assets::{FontKey, HandleMap, ImageKey, DEFAULT_FONT_KEY},
+    practice::Practice,
     score::Difficulty,
+    tutorial::{scripted_cycle, Tutorial},
 };
 
 pub const NEXT_WEAPON_CYCLE_INTERVAL_MAX: u64 = 15000;
@@ -30,7 +32,12 @@ pub const NEXT_WEAPON_CYCLE_INTERVAL_DELTA: u64 = 10000;
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(
         OnEnter(Screen::Playing),
-        (setup_cycle, setup_cycle_ui).chain(),
+        (
+            setup_cycle,
+            // Nothing to announce when practicing a single mode
+            setup_cycle_ui.run_if(not(resource_exists::<Practice>)),
+        )
+            .chain(),
     );
 
     app.add_systems(
@@ -52,9 +59,27 @@ pub struct Cycle {
     pub current_mode: ArenaMode,
     pub next_mode: ArenaMode,
     pub next_mode_timer: Timer,
+    /// Weapon changes since the start of the run
+    pub completed_cycles: u32,
 }
 
-fn setup_cycle(mut commands: Commands, mut next_arena_mode: ResMut<NextState<ArenaMode>>) {
+fn setup_cycle(
+    mut commands: Commands,
+    tutorial: Option<Res<Tutorial>>,
+    practice: Option<Res<Practice>>,
+    mut next_arena_mode: ResMut<NextState<ArenaMode>>,
+) {
+    let forced_cycle = match (tutorial, practice) {
+        (Some(_), _) => Some(scripted_cycle()),
+        (None, Some(practice)) => Some(practice.cycle()),
+        (None, None) => None,
+    };
+    if let Some(cycle) = forced_cycle {
+        next_arena_mode.set(cycle.current_mode);
+        commands.insert_resource(cycle);
+        return;
+    }
+
     let random_arena_mode: ArenaMode = rand::random();
     next_arena_mode.set(random_arena_mode.clone());
 
@@ -66,6 +91,7 @@ fn setup_cycle(mut commands: Commands, mut next_arena_mode: ResMut<NextState<Are
             Duration::from_millis(NEXT_WEAPON_CYCLE_INTERVAL_MAX),
             TimerMode::Repeating,
         ),
+        completed_cycles: 0,
     });
 }
 
@@ -138,6 +164,7 @@ fn update_cycle(
         ));
         next_arena_mode.set(cycle.next_mode.clone());
         cycle.current_mode = cycle.next_mode;
+        cycle.completed_cycles += 1;
         cycle.next_mode = get_random_different_mode(&cycle.next_mode);
     }
 }
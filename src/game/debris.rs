@@ -0,0 +1,231 @@
+//! Lifecycle of the debris left by slices and shatters: grouping, budget, sinking and cleanup.
+
+use std::time::Duration;
+
+use bevy::{
+    app::{App, Update},
+    asset::{Assets, Handle},
+    core::Name,
+    ecs::component::{ComponentHooks, StorageType},
+    math::Vec3,
+    prelude::{
+        Children, Commands, Component, DespawnRecursiveExt, Entity, IntoSystemConfigs, Mesh, Query,
+        Res, Resource, SpatialBundle, StateScoped, Transform, With, Without,
+    },
+    reflect::Reflect,
+    time::{Time, Timer, TimerMode},
+};
+use bevy_rapier3d::prelude::{ColliderDisabled, RigidBody};
+
+use crate::{screen::Screen, AppSet};
+
+use super::breakable::{DamageBreakable, BREAK_DAMAGE};
+
+/// Max number of debris pieces simulated at once. Oldest groups are culled first.
+pub const MAX_DEBRIS_PIECES: usize = 150;
+pub const DEBRIS_SINK_DURATION_MS: u64 = 1200;
+/// How far below their resting place the debris sink before being despawned
+pub const DEBRIS_SINK_DEPTH: f32 = 1.5;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<DebrisBudget>();
+    app.register_type::<DebrisGroup>();
+    app.register_type::<DebrisPiece>();
+    app.register_type::<SinkingDebris>();
+    app.init_resource::<DebrisBudget>();
+
+    app.add_systems(
+        Update,
+        (
+            expire_debris_groups,
+            enforce_debris_budget,
+            sink_debris,
+            despawn_empty_debris_groups,
+        )
+            .chain()
+            .in_set(AppSet::Update),
+    );
+}
+
+#[derive(Resource, Debug, Reflect)]
+pub struct DebrisBudget {
+    pub max_pieces: usize,
+    /// Used to order the groups by age
+    next_group_index: u64,
+}
+impl Default for DebrisBudget {
+    fn default() -> Self {
+        Self {
+            max_pieces: MAX_DEBRIS_PIECES,
+            next_group_index: 0,
+        }
+    }
+}
+
+/// What happens to the pieces of a group when its lifetime ends
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum DebrisExpiry {
+    /// The pieces sink into the ground and are despawned
+    Sink,
+    /// The pieces shatter into smaller debris, they must be [`Breakable`](super::breakable::Breakable)
+    Shatter,
+}
+
+/// Parent of debris pieces spawned together, sharing a single timer
+#[derive(Component, Debug, Reflect)]
+pub struct DebrisGroup {
+    lifetime: Timer,
+    expiry: DebrisExpiry,
+    index: u64,
+}
+
+/// A physics object owned by a [`DebrisGroup`]
+///
+/// When `owns_mesh` is set, the mesh asset of the piece was generated for it and is removed with it.
+#[derive(Debug, Clone, Copy, Default, Reflect)]
+pub struct DebrisPiece {
+    pub owns_mesh: bool,
+}
+impl Component for DebrisPiece {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+    fn register_component_hooks(hooks: &mut ComponentHooks) {
+        hooks.on_remove(|mut world, entity, _comp_id| {
+            let Some(piece) = world.get::<DebrisPiece>(entity).copied() else {
+                return;
+            };
+            if !piece.owns_mesh {
+                return;
+            }
+            let Some(mesh_handle) = world.get::<Handle<Mesh>>(entity).cloned() else {
+                return;
+            };
+            world.resource_mut::<Assets<Mesh>>().remove(&mesh_handle);
+        });
+    }
+}
+
+#[derive(Component, Debug, Reflect)]
+struct SinkingDebris {
+    timer: Timer,
+    start: Transform,
+}
+
+/// Spawns an empty group at the origin, debris pieces should be added as its children
+pub fn spawn_debris_group(
+    commands: &mut Commands,
+    budget: &mut DebrisBudget,
+    name: &'static str,
+    lifetime: Duration,
+    expiry: DebrisExpiry,
+) -> Entity {
+    let index = budget.next_group_index;
+    budget.next_group_index += 1;
+    commands
+        .spawn((
+            Name::new(name),
+            StateScoped(Screen::Playing),
+            SpatialBundle::default(),
+            DebrisGroup {
+                lifetime: Timer::new(lifetime, TimerMode::Once),
+                expiry,
+                index,
+            },
+        ))
+        .id()
+}
+
+fn start_sinking(commands: &mut Commands, entity: Entity, transform: &Transform) {
+    commands.entity(entity).insert((
+        RigidBody::KinematicPositionBased,
+        ColliderDisabled,
+        SinkingDebris {
+            timer: Timer::new(
+                Duration::from_millis(DEBRIS_SINK_DURATION_MS),
+                TimerMode::Once,
+            ),
+            start: *transform,
+        },
+    ));
+}
+
+fn expire_debris_groups(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut groups: Query<(&mut DebrisGroup, Option<&Children>)>,
+    pieces: Query<&Transform, (With<DebrisPiece>, Without<SinkingDebris>)>,
+) {
+    for (mut group, children) in groups.iter_mut() {
+        group.lifetime.tick(time.delta());
+        if !group.lifetime.just_finished() {
+            continue;
+        }
+        for &piece in children.into_iter().flatten() {
+            let Ok(transform) = pieces.get(piece) else {
+                continue;
+            };
+            match group.expiry {
+                DebrisExpiry::Sink => start_sinking(&mut commands, piece, transform),
+                DebrisExpiry::Shatter => commands.trigger(DamageBreakable {
+                    entity: piece,
+                    damage: BREAK_DAMAGE,
+                    impact_point: transform.translation,
+                    impact_velocity: Vec3::ZERO,
+                }),
+            }
+        }
+    }
+}
+
+/// Sinks the oldest groups when there are too many pieces
+fn enforce_debris_budget(
+    mut commands: Commands,
+    budget: Res<DebrisBudget>,
+    groups: Query<(Entity, &DebrisGroup, Option<&Children>)>,
+    pieces: Query<&Transform, (With<DebrisPiece>, Without<SinkingDebris>)>,
+) {
+    let mut active_pieces = pieces.iter().count();
+    if active_pieces <= budget.max_pieces {
+        return;
+    }
+    let mut groups: Vec<_> = groups.iter().collect();
+    groups.sort_by_key(|(_, group, _)| group.index);
+    for (_, _, children) in groups {
+        if active_pieces <= budget.max_pieces {
+            break;
+        }
+        for &piece in children.into_iter().flatten() {
+            if let Ok(transform) = pieces.get(piece) {
+                start_sinking(&mut commands, piece, transform);
+                active_pieces -= 1;
+            }
+        }
+    }
+}
+
+fn sink_debris(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut sinking_pieces: Query<(Entity, &mut SinkingDebris, &mut Transform)>,
+) {
+    for (entity, mut sinking, mut transform) in sinking_pieces.iter_mut() {
+        sinking.timer.tick(time.delta());
+        if sinking.timer.finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+        let progress = sinking.timer.fraction();
+        transform.translation = sinking.start.translation - Vec3::Y * DEBRIS_SINK_DEPTH * progress;
+        transform.scale = sinking.start.scale * (1. - progress);
+    }
+}
+
+fn despawn_empty_debris_groups(
+    mut commands: Commands,
+    groups: Query<(Entity, Option<&Children>), With<DebrisGroup>>,
+) {
+    for (entity, children) in groups.iter() {
+        if children.map_or(true, |children| children.is_empty()) {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
@@ -0,0 +1,230 @@
+//! Rules of a run, on top of the arena modes.
+
+use std::time::Duration;
+
+use bevy::{
+    app::{App, Update},
+    prelude::{
+        in_state, resource_exists, BuildChildren, Commands, Component, IntoSystemConfigs, OnEnter,
+        OnExit, Query, Res, ResMut, Resource, StateScoped, Trigger, With,
+    },
+    reflect::Reflect,
+    text::Text,
+    time::{Real, Time, Timer, TimerMode},
+    utils::HashMap,
+};
+
+use crate::{screen::Screen, ui::prelude::*, AppSet};
+
+use super::{
+    arena::ArenaMode,
+    assets::{FontKey, HandleMap, DEFAULT_FONT_KEY},
+    cycle::Cycle,
+    score::{EndRun, Score, ScoreAction, ScoreActionType},
+};
+
+pub const TIME_ATTACK_DURATION_MS: u64 = 120_000;
+/// Weapon changes in a marathon run
+pub const MARATHON_CYCLES: u32 = 6;
+/// Scores kept for each game mode
+pub const LEADERBOARD_SIZE: usize = 5;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<GameMode>();
+    app.register_type::<GameModeKind>();
+    app.register_type::<Leaderboards>();
+    app.init_resource::<Leaderboards>();
+
+    app.add_systems(
+        OnEnter(Screen::Playing),
+        setup_game_mode_ui.run_if(resource_exists::<GameMode>),
+    );
+    app.add_systems(
+        OnEnter(ArenaMode::GameOver),
+        record_run.run_if(resource_exists::<GameMode>),
+    );
+    app.add_systems(OnExit(Screen::Playing), end_game_mode);
+    app.add_systems(
+        Update,
+        (
+            tick_game_mode.in_set(AppSet::TickTimers),
+            (end_marathon, update_game_mode_ui).in_set(AppSet::Update),
+        )
+            .run_if(in_state(Screen::Playing).and_then(resource_exists::<GameMode>)),
+    );
+
+    app.observe(end_sudden_death);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum GameModeKind {
+    /// Play until the score drops below zero
+    Endless,
+    /// Best score before the time runs out
+    TimeAttack,
+    /// The first miss ends the run
+    SuddenDeath,
+    /// A fixed number of weapon changes
+    Marathon,
+}
+impl GameModeKind {
+    pub const ALL: [GameModeKind; 4] = [
+        GameModeKind::Endless,
+        GameModeKind::TimeAttack,
+        GameModeKind::SuddenDeath,
+        GameModeKind::Marathon,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            GameModeKind::Endless => "Endless",
+            GameModeKind::TimeAttack => "Time Attack",
+            GameModeKind::SuddenDeath => "Sudden Death",
+            GameModeKind::Marathon => "Marathon",
+        }
+    }
+
+    /// A score below zero is a game over
+    pub fn ends_below_zero(&self) -> bool {
+        !matches!(self, GameModeKind::TimeAttack)
+    }
+}
+
+/// Present during a run started from the mode selection, insert it before entering [`Screen::Playing`]
+#[derive(Resource, Debug, Reflect)]
+pub struct GameMode {
+    pub kind: GameModeKind,
+    /// Time left in a time attack
+    time_limit: Option<Timer>,
+}
+impl GameMode {
+    pub fn new(kind: GameModeKind) -> Self {
+        let time_limit = (kind == GameModeKind::TimeAttack).then(|| {
+            Timer::new(
+                Duration::from_millis(TIME_ATTACK_DURATION_MS),
+                TimerMode::Once,
+            )
+        });
+        Self { kind, time_limit }
+    }
+}
+
+/// Best scores of each game mode, best first
+#[derive(Resource, Debug, Default, Reflect)]
+pub struct Leaderboards {
+    scores: HashMap<GameModeKind, Vec<i32>>,
+}
+impl Leaderboards {
+    pub fn scores(&self, kind: GameModeKind) -> &[i32] {
+        self.scores
+            .get(&kind)
+            .map_or(&[], |scores| scores.as_slice())
+    }
+
+    pub fn record(&mut self, kind: GameModeKind, score: i32) {
+        let scores = self.scores.entry(kind).or_default();
+        let index = scores.partition_point(|best| *best >= score);
+        scores.insert(index, score);
+        scores.truncate(LEADERBOARD_SIZE);
+    }
+}
+
+#[derive(Component)]
+pub struct GameModeText;
+
+fn setup_game_mode_ui(
+    mut commands: Commands,
+    game_mode: Res<GameMode>,
+    font_handles: Res<HandleMap<FontKey>>,
+) {
+    let font = font_handles.get(&DEFAULT_FONT_KEY).unwrap().clone();
+    commands
+        .top_left_ui_root()
+        .insert(StateScoped(Screen::Playing))
+        .with_children(|children| {
+            children.dynamic_label_with_marker(
+                format!("{}: ", game_mode.kind.name()),
+                "",
+                GameModeText,
+                font.clone_weak(),
+            );
+        });
+}
+
+fn end_game_mode(mut commands: Commands) {
+    commands.remove_resource::<GameMode>();
+}
+
+/// Time attack runs end on the timer with their final score, the other modes end below zero and keep their peak score
+fn record_run(game_mode: Res<GameMode>, score: Res<Score>, mut leaderboards: ResMut<Leaderboards>) {
+    let run_score = match game_mode.kind {
+        GameModeKind::TimeAttack => score.current(),
+        _ => score.highscore() as i32,
+    };
+    leaderboards.record(game_mode.kind, run_score);
+}
+
+/// On real time, so that slow motion and hit-stop do not extend the run
+fn tick_game_mode(
+    mut commands: Commands,
+    real_time: Res<Time<Real>>,
+    mut game_mode: ResMut<GameMode>,
+) {
+    let Some(time_limit) = &mut game_mode.time_limit else {
+        return;
+    };
+    time_limit.tick(real_time.delta());
+    if time_limit.just_finished() {
+        commands.trigger(EndRun);
+    }
+}
+
+fn end_marathon(mut commands: Commands, game_mode: Res<GameMode>, cycle: Res<Cycle>) {
+    if game_mode.kind == GameModeKind::Marathon
+        && cycle.completed_cycles >= MARATHON_CYCLES
+        && cycle.current_mode != ArenaMode::GameOver
+    {
+        commands.trigger(EndRun);
+    }
+}
+
+fn end_sudden_death(
+    trigger: Trigger<ScoreAction>,
+    mut commands: Commands,
+    game_mode: Option<Res<GameMode>>,
+) {
+    let Some(game_mode) = game_mode else {
+        return;
+    };
+    if game_mode.kind == GameModeKind::SuddenDeath && trigger.event().action == ScoreActionType::Bad
+    {
+        commands.trigger(EndRun);
+    }
+}
+
+fn update_game_mode_ui(
+    game_mode: Res<GameMode>,
+    cycle: Res<Cycle>,
+    mut text_query: Query<&mut Text, With<GameModeText>>,
+) {
+    let Ok(mut text) = text_query.get_single_mut() else {
+        return;
+    };
+    let value = match game_mode.kind {
+        GameModeKind::Endless => "stay above 0".to_string(),
+        GameModeKind::TimeAttack => game_mode
+            .time_limit
+            .as_ref()
+            .map_or(String::new(), |time_limit| {
+                format!("{}s left", time_limit.remaining().as_secs())
+            }),
+        GameModeKind::SuddenDeath => "no miss allowed".to_string(),
+        GameModeKind::Marathon => format!(
+            "{} weapon changes left",
+            MARATHON_CYCLES.saturating_sub(cycle.completed_cycles)
+        ),
+    };
+    if text.sections[1].value != value {
+        text.sections[1].value = value;
+    }
+}
@@ -2,17 +2,29 @@
 
 use bevy::prelude::*;
 
+pub mod accessibility;
 pub mod arena;
 pub mod assets;
 pub mod audio;
+pub mod ballistics;
+pub mod breakable;
 pub mod camera;
+pub mod camera_director;
 pub mod cycle;
+pub mod debris;
+pub mod game_mode;
 pub mod player_animation;
+pub mod player_camera;
+pub mod powerups;
+pub mod practice;
+pub mod raycasting;
 pub mod score;
 pub mod shattering;
 pub mod shield;
 pub mod spawn;
 pub mod sword;
+pub mod time_scale;
+pub mod tutorial;
 
 pub(super) fn plugin(app: &mut App) {
     app.add_plugins((
@@ -26,5 +38,19 @@ pub(super) fn plugin(app: &mut App) {
         player_animation::plugin,
         shattering::plugin,
         cycle::plugin,
+        ballistics::plugin,
+        debris::plugin,
+        breakable::plugin,
+    ));
+    app.add_plugins((
+        powerups::plugin,
+        time_scale::plugin,
+        camera_director::plugin,
+        accessibility::plugin,
+        player_camera::plugin,
+        raycasting::plugin,
+        tutorial::plugin,
+        practice::plugin,
+        game_mode::plugin,
     ));
 }
@@ -1,6 +1,6 @@
 //! Plays animations from a skinned glTF.
 
-use std::time::Duration;
+use std::{collections::HashMap, time::Duration};
 
 use bevy::{
     animation::{animate_targets, RepeatAnimation},
@@ -16,34 +16,103 @@ pub(super) fn plugin(app: &mut App) {
     );
     app.add_systems(
         Update,
-        (attach_player_animations, attach_equipments, return_to_idle),
+        (
+            attach_player_animations,
+            attach_equipments,
+            return_to_idle,
+            emit_animation_markers,
+        ),
     );
     app.observe(play_slash_animation);
     app.observe(look_towards_sliced_dummy);
+    app.observe(play_block_animation);
 }
 
 use crate::screen::Screen;
 
 use super::{
     assets::{AnimationKey, HandleMap},
+    shield::blocking::ImminentHit,
     spawn::player::Player,
-    sword::slicing::SliceEvent,
+    sword::slicing::{SlashStarted, SliceEvent},
 };
 
 pub const RIGHT_HAND_SLOT: &str = "EquipmentSlot.R";
 pub const SHIELD_SLOT: &str = "mixamorig:LeftForeArm";
 pub const HEAD_SLOT: &str = "EquipmentSlot.H";
 
-pub const PLAYER_SLASH_ANIMATION_SPEED: f32 = 3.4;
+/// Delay between the slice input and the blade contact frame of the slash animation
+pub const PLAYER_SLASH_CONTACT_DELAY_MS: u64 = 90;
+pub const PLAYER_SLASH_MIN_ANIMATION_SPEED: f32 = 1.;
+pub const PLAYER_SLASH_MAX_ANIMATION_SPEED: f32 = 6.;
+pub const PLAYER_BLOCK_TRANSITION_MS: u64 = 80;
+
+/// Notable frames of the animation clips
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum AnimationMarker {
+    /// The blade of the slash reaches its target
+    BladeContact,
+}
+
+/// Markers placed on the clips, as a fraction of the clip duration
+pub const ANIMATION_MARKERS: [(AnimationKey, AnimationMarker, f32); 1] = [(
+    AnimationKey::GladiatorSlash,
+    AnimationMarker::BladeContact,
+    0.3,
+)];
+
+/// Triggered on an `AnimationPlayer` entity when one of its playing clips crosses a marker
+#[derive(Event, Debug, Clone, Copy)]
+pub struct AnimationMarkerReached {
+    pub marker: AnimationMarker,
+}
+
+/// Swing of the slash animation currently played by this animation player
+#[derive(Component, Debug, Clone, Copy)]
+pub struct SlashSwing(pub u32);
+
+/// Seek times of the marked clips on the previous frame
+#[derive(Component, Debug, Default)]
+pub struct AnimationMarkersTracker {
+    previous_seek_times: HashMap<AnimationNodeIndex, f32>,
+}
 
 #[derive(Resource)]
 pub struct PlayerAnimations {
     _walk_anim: AnimationNodeIndex,
     idle_anim: AnimationNodeIndex,
     slash_anim: AnimationNodeIndex,
+    pub throw_anim: AnimationNodeIndex,
+    block_anim: AnimationNodeIndex,
     graph: Handle<AnimationGraph>,
 }
 
+impl PlayerAnimations {
+    pub fn node(&self, key: AnimationKey) -> Option<AnimationNodeIndex> {
+        match key {
+            AnimationKey::GladiatorFightIdle => Some(self.idle_anim),
+            AnimationKey::GladiatorWalk => Some(self._walk_anim),
+            AnimationKey::GladiatorSlash => Some(self.slash_anim),
+            AnimationKey::GladiatorThrow => Some(self.throw_anim),
+            AnimationKey::GladiatorBlock => Some(self.block_anim),
+        }
+    }
+}
+
+/// Time of `marker` in the clip of `key`, in seconds
+pub fn marker_time(
+    key: AnimationKey,
+    marker: AnimationMarker,
+    clips: &Assets<AnimationClip>,
+    anim_handles: &HandleMap<AnimationKey>,
+) -> Option<f32> {
+    let (_, _, ratio) = ANIMATION_MARKERS
+        .iter()
+        .find(|(marker_key, m, _)| *marker_key == key && *m == marker)?;
+    let clip = clips.get(anim_handles.get(&key)?)?;
+    Some(clip.duration() * ratio)
+}
+
 fn setup_player_animations(
     mut commands: Commands,
     anim_handles: Res<HandleMap<AnimationKey>>,
@@ -65,12 +134,24 @@ fn setup_player_animations(
         1.0,
         graph.root,
     );
+    let throw_anim = graph.add_clip(
+        anim_handles[&AnimationKey::GladiatorThrow].clone_weak(),
+        1.0,
+        graph.root,
+    );
+    let block_anim = graph.add_clip(
+        anim_handles[&AnimationKey::GladiatorBlock].clone_weak(),
+        1.0,
+        graph.root,
+    );
     let graph = graphs.add(graph);
 
     commands.insert_resource(PlayerAnimations {
         idle_anim,
         _walk_anim: walk_anim,
         slash_anim,
+        throw_anim,
+        block_anim,
         graph: graph.clone(),
     });
 }
@@ -96,7 +177,8 @@ fn attach_player_animations(
         commands
             .entity(entity)
             .insert(animations.graph.clone())
-            .insert(transitions);
+            .insert(transitions)
+            .insert(AnimationMarkersTracker::default());
     }
 }
 
@@ -173,22 +255,129 @@ pub fn find_child_with_name_containing(
     None
 }
 
-// TODO With<Player>, .. But only the scene root has the Player marker, not the animation player entity
+/// Once per stroke, a multi-slice does not restart the swing
 fn play_slash_animation(
-    _trigger: Trigger<SliceEvent>,
+    trigger: Trigger<SlashStarted>,
+    mut commands: Commands,
     animations: Res<PlayerAnimations>,
-    mut players_query: Query<(Entity, &mut AnimationPlayer, &mut AnimationTransitions)>,
+    clips: Res<Assets<AnimationClip>>,
+    anim_handles: Res<HandleMap<AnimationKey>>,
+    player_query: Query<Entity, With<Player>>,
+    children_query: Query<&Children>,
+    mut anim_players_query: Query<(&mut AnimationPlayer, &mut AnimationTransitions)>,
 ) {
-    for (_entity, mut animation_player, mut transitions) in players_query.iter_mut() {
-        // info!("Transition to slash animation");
+    let Some(anim_player_entity) =
+        find_player_animation_player(&player_query, &children_query, &anim_players_query)
+    else {
+        return;
+    };
+    let Ok((mut animation_player, mut transitions)) =
+        anim_players_query.get_mut(anim_player_entity)
+    else {
+        return;
+    };
+
+    // Play the clip at the speed that brings the blade in contact after the expected delay
+    let contact_delay = Duration::from_millis(PLAYER_SLASH_CONTACT_DELAY_MS).as_secs_f32();
+    let speed = marker_time(
+        AnimationKey::GladiatorSlash,
+        AnimationMarker::BladeContact,
+        &clips,
+        &anim_handles,
+    )
+    .map(|contact_time| contact_time / contact_delay)
+    .unwrap_or(PLAYER_SLASH_MAX_ANIMATION_SPEED)
+    .clamp(
+        PLAYER_SLASH_MIN_ANIMATION_SPEED,
+        PLAYER_SLASH_MAX_ANIMATION_SPEED,
+    );
+
+    transitions
+        .play(
+            &mut animation_player,
+            animations.slash_anim,
+            Duration::from_millis(50),
+        )
+        .set_speed(speed);
+    commands
+        .entity(anim_player_entity)
+        .insert(SlashSwing(trigger.event().swing));
+}
+
+/// Only the scene root has the [`Player`] marker, finds the animation player among its descendants
+fn find_player_animation_player(
+    player_query: &Query<Entity, With<Player>>,
+    children_query: &Query<&Children>,
+    anim_players_query: &Query<(&mut AnimationPlayer, &mut AnimationTransitions)>,
+) -> Option<Entity> {
+    let player = player_query.get_single().ok()?;
+    children_query
+        .iter_descendants(player)
+        .find(|descendant| anim_players_query.contains(*descendant))
+}
+
+fn play_block_animation(
+    _trigger: Trigger<ImminentHit>,
+    animations: Res<PlayerAnimations>,
+    player_query: Query<Entity, With<Player>>,
+    children_query: Query<&Children>,
+    mut anim_players_query: Query<(&mut AnimationPlayer, &mut AnimationTransitions)>,
+) {
+    let Some(anim_player_entity) =
+        find_player_animation_player(&player_query, &children_query, &anim_players_query)
+    else {
+        return;
+    };
+    if let Ok((mut animation_player, mut transitions)) =
+        anim_players_query.get_mut(anim_player_entity)
+    {
+        // Several jugs can arrive together, keep the current block going
+        if transitions.get_main_animation() == Some(animations.block_anim)
+            && animation_player
+                .animation(animations.block_anim)
+                .is_some_and(|anim| !anim.is_finished())
+        {
+            return;
+        }
         transitions
             .play(
                 &mut animation_player,
-                animations.slash_anim,
-                Duration::from_millis(50),
+                animations.block_anim,
+                Duration::from_millis(PLAYER_BLOCK_TRANSITION_MS),
             )
-            .set_speed(PLAYER_SLASH_ANIMATION_SPEED);
-        // TODO Getting the animation duration here would be great
+            .set_speed(1.0);
+    }
+}
+
+fn emit_animation_markers(
+    mut commands: Commands,
+    animations: Res<PlayerAnimations>,
+    clips: Res<Assets<AnimationClip>>,
+    anim_handles: Res<HandleMap<AnimationKey>>,
+    mut players_query: Query<(Entity, &AnimationPlayer, &mut AnimationMarkersTracker)>,
+) {
+    for (entity, animation_player, mut tracker) in players_query.iter_mut() {
+        for (key, marker, _) in ANIMATION_MARKERS {
+            let Some(node) = animations.node(key) else {
+                continue;
+            };
+            let Some(animation) = animation_player.animation(node) else {
+                tracker.previous_seek_times.remove(&node);
+                continue;
+            };
+            let Some(time) = marker_time(key, marker, &clips, &anim_handles) else {
+                continue;
+            };
+            let seek_time = animation.seek_time();
+            // A seek time going backwards means that the clip was restarted
+            let previous_seek_time = match tracker.previous_seek_times.insert(node, seek_time) {
+                Some(previous) if previous <= seek_time => previous,
+                _ => 0.,
+            };
+            if previous_seek_time < time && seek_time >= time {
+                commands.trigger_targets(AnimationMarkerReached { marker }, entity);
+            }
+        }
     }
 }
 
@@ -0,0 +1,158 @@
+//! Limited camera control given to the player in the arena modes.
+//!
+//! Right-drag orbits around the preset and the scroll wheel zooms, the camera springs back to the preset afterwards.
+//! The left button stays free for the slicing and the cursor raycasts.
+
+use std::time::Duration;
+
+use bevy::{
+    app::{App, Update},
+    input::{
+        mouse::{MouseMotion, MouseScrollUnit, MouseWheel},
+        ButtonInput,
+    },
+    math::Vec2,
+    prelude::{
+        in_state, Commands, Component, Entity, EventReader, IntoSystemConfigs, MouseButton, Query,
+        Res, Trigger, With, Without,
+    },
+    time::{Real, Time, Timer, TimerMode},
+};
+
+use crate::{screen::Screen, AppSet};
+
+use super::{
+    camera::{update_pan_orbit_camera, PanOrbitState},
+    camera_director::{CameraTransition, CurrentCameraPreset, SetCameraPreset},
+};
+
+/// Radians per pixel of mouse motion
+pub const PLAYER_CAMERA_ORBIT_SENSITIVITY: f32 = 0.004;
+/// Zoom exponent per scroll line, and per scroll pixel for touchpads
+pub const PLAYER_CAMERA_ZOOM_PER_LINE: f32 = 0.08;
+pub const PLAYER_CAMERA_ZOOM_PER_PIXEL: f32 = 0.005;
+/// How fast the camera goes back to the preset, higher is faster
+pub const PLAYER_CAMERA_SPRING_BACK_SPEED: f32 = 4.;
+/// The zoom springs back after this long without scrolling
+pub const PLAYER_CAMERA_ZOOM_HOLD_MS: u64 = 1500;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        control_player_camera
+            .in_set(AppSet::RecordInput)
+            .before(update_pan_orbit_camera)
+            .run_if(in_state(Screen::Playing)),
+    );
+
+    app.observe(reset_player_camera);
+}
+
+/// Run condition, true while the player orbits the camera
+pub fn player_camera_dragging(mouse: Res<ButtonInput<MouseButton>>) -> bool {
+    mouse.pressed(MouseButton::Right)
+}
+
+/// Offsets from the current preset
+#[derive(Component, Debug)]
+struct PlayerCameraOffset {
+    yaw: f32,
+    /// Factor applied on the preset radius
+    zoom: f32,
+    zoom_hold: Timer,
+}
+impl Default for PlayerCameraOffset {
+    fn default() -> Self {
+        Self {
+            yaw: 0.,
+            zoom: 1.,
+            zoom_hold: Timer::new(
+                Duration::from_millis(PLAYER_CAMERA_ZOOM_HOLD_MS),
+                TimerMode::Once,
+            ),
+        }
+    }
+}
+
+fn reset_player_camera(
+    _trigger: Trigger<SetCameraPreset>,
+    mut commands: Commands,
+    camera_query: Query<Entity, With<PanOrbitState>>,
+) {
+    for camera in camera_query.iter() {
+        commands
+            .entity(camera)
+            .insert(PlayerCameraOffset::default());
+    }
+}
+
+/// Moves `value` towards `target`, snapping when close enough to stop refreshing the camera
+fn spring_back(value: f32, target: f32, delta_seconds: f32) -> f32 {
+    let value =
+        value + (target - value) * (1. - (-PLAYER_CAMERA_SPRING_BACK_SPEED * delta_seconds).exp());
+    if (value - target).abs() < 1e-3 {
+        target
+    } else {
+        value
+    }
+}
+
+fn control_player_camera(
+    real_time: Res<Time<Real>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    mut motion_events: EventReader<MouseMotion>,
+    mut scroll_events: EventReader<MouseWheel>,
+    mut camera_query: Query<
+        (
+            &CurrentCameraPreset,
+            &mut PlayerCameraOffset,
+            &mut PanOrbitState,
+        ),
+        Without<CameraTransition>,
+    >,
+) {
+    let motion: Vec2 = motion_events.read().map(|ev| ev.delta).sum();
+    let scroll: f32 = scroll_events
+        .read()
+        .map(|ev| match ev.unit {
+            MouseScrollUnit::Line => ev.y * PLAYER_CAMERA_ZOOM_PER_LINE,
+            MouseScrollUnit::Pixel => ev.y * PLAYER_CAMERA_ZOOM_PER_PIXEL,
+        })
+        .sum();
+    let dragging = mouse.pressed(MouseButton::Right);
+    let delta_seconds = real_time.delta_seconds();
+
+    for (current_preset, mut offset, mut cam_state) in camera_query.iter_mut() {
+        let preset = current_preset.0.preset();
+        let Some(limits) = &preset.player_control else {
+            continue;
+        };
+        let previous_offset = (offset.yaw, offset.zoom);
+
+        if dragging {
+            offset.yaw = (offset.yaw - motion.x * PLAYER_CAMERA_ORBIT_SENSITIVITY)
+                .clamp(-limits.max_yaw_offset, limits.max_yaw_offset);
+        } else {
+            offset.yaw = spring_back(offset.yaw, 0., delta_seconds);
+        }
+
+        if scroll != 0. {
+            // Scrolling up zooms in
+            let (min_zoom, max_zoom) = limits.zoom_range;
+            offset.zoom = (offset.zoom * (-scroll).exp()).clamp(min_zoom, max_zoom);
+            offset.zoom_hold.reset();
+        } else {
+            offset.zoom_hold.tick(real_time.delta());
+            if offset.zoom_hold.finished() && !dragging {
+                offset.zoom = spring_back(offset.zoom, 1., delta_seconds);
+            }
+        }
+
+        // Leave the state alone when nothing moves, other controllers may use it
+        if (offset.yaw, offset.zoom) != previous_offset {
+            cam_state.yaw = preset.pose.yaw + offset.yaw;
+            cam_state.radius = preset.pose.radius * offset.zoom;
+            cam_state.needs_transform_refresh = true;
+        }
+    }
+}
@@ -0,0 +1,555 @@
+//! Temporary power-ups, dropped as pickups by perfect slices and broken props.
+
+use std::time::Duration;
+
+use bevy::{
+    color::palettes::css::{DEEP_SKY_BLUE, GOLD, LIME, MEDIUM_PURPLE},
+    prelude::*,
+    utils::HashMap,
+};
+use bevy_rapier3d::prelude::{
+    ActiveCollisionTypes, ActiveEvents, Collider, CollisionEvent, RapierConfiguration, RigidBody,
+    Velocity,
+};
+use bevy_tweening::{
+    lens::TransformPositionLens, Animator, EaseFunction, RepeatCount, RepeatStrategy, Tween,
+};
+use rand::Rng;
+
+use crate::{screen::Screen, ui::prelude::*, AppSet};
+
+use super::{
+    arena::ArenaMode,
+    assets::{FontKey, HandleMap, DEFAULT_FONT_KEY},
+    ballistics::ballistic_launch_velocity,
+    breakable::{Breakable, DamageBreakable, BREAK_DAMAGE},
+    cycle::Cycle,
+    score::{ScoreAction, ScoreActionType, ScoreModifiers},
+    shield::throwers::THROW_TARGET_TORSO_HEIGHT,
+    spawn::{player::Player, prop::PropBroken, shield::Shield},
+    sword::slicing::{SliceDepth, SliceEvent, SliceSettings, Sliceable},
+    time_scale::{TimeScale, TimeScaleSource},
+};
+
+/// Probability for a perfect slice to drop a pickup
+pub const PERFECT_SLICE_DROP_CHANCE: f64 = 0.15;
+/// Probability for a broken prop to drop a pickup
+pub const BROKEN_PROP_DROP_CHANCE: f64 = 0.5;
+
+/// Pickups not collected after this delay disappear
+pub const PICKUP_LIFETIME_MS: u64 = 6000;
+pub const PICKUP_RADIUS: f32 = 0.4;
+/// In sword mode, pickups float this high above where they dropped
+pub const PICKUP_FLOAT_HEIGHT: f32 = 2.;
+pub const PICKUP_BOB_HEIGHT: f32 = 0.3;
+pub const PICKUP_BOB_PERIOD_MS: u64 = 900;
+/// In shield mode, pickups are thrown at the player
+pub const PICKUP_FLIGHT_TIME_MS: u64 = 1400;
+
+/// Time scale during a slow-motion
+pub const SLOW_MOTION_TIME_SCALE: f32 = 0.5;
+/// Distance from the cursor at which a wide blade still cuts
+pub const WIDE_BLADE_HALF_WIDTH: f32 = 0.6;
+pub const SCORE_DOUBLER_MULTIPLIER: f32 = 2.;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<PowerUpKind>();
+    app.register_type::<Pickup>();
+    app.register_type::<ActivePowerUps>();
+    app.init_resource::<ActivePowerUps>();
+    app.init_resource::<PickupCachedData>();
+
+    app.add_systems(OnEnter(Screen::Playing), setup_power_ups_ui);
+    app.add_systems(OnEnter(ArenaMode::GameOver), end_all_power_ups);
+    app.add_systems(OnExit(Screen::Playing), end_all_power_ups);
+    app.add_systems(
+        Update,
+        (
+            (tick_power_ups, despawn_expired_pickups).in_set(AppSet::TickTimers),
+            (
+                collect_blocked_pickups.run_if(in_state(ArenaMode::Shield)),
+                update_power_ups_ui,
+            )
+                .in_set(AppSet::Update),
+        )
+            .run_if(in_state(Screen::Playing)),
+    );
+
+    app.observe(drop_on_perfect_slice);
+    app.observe(drop_on_broken_prop);
+    app.observe(spawn_pickup);
+    app.observe(collect_sliced_pickup);
+    app.observe(activate_power_up);
+    app.observe(apply_power_up_effect);
+    app.observe(remove_power_up_effect);
+    app.observe(add_power_up_hud_entry);
+    app.observe(remove_power_up_hud_entry);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum PowerUpKind {
+    /// Slows down the game time
+    SlowMotion,
+    /// The blade cuts everything around the cursor
+    WideBlade,
+    /// The shield turns by itself towards the closest jug
+    AutoBlock,
+    /// Doubles the points earned
+    ScoreDoubler,
+}
+
+impl PowerUpKind {
+    pub const ALL: [PowerUpKind; 4] = [
+        PowerUpKind::SlowMotion,
+        PowerUpKind::WideBlade,
+        PowerUpKind::AutoBlock,
+        PowerUpKind::ScoreDoubler,
+    ];
+
+    pub fn archetype(&self) -> &'static PowerUpArchetype {
+        POWER_UP_ARCHETYPES
+            .iter()
+            .find(|archetype| archetype.kind == *self)
+            .unwrap()
+    }
+
+    /// Only drops power-ups that are useful in `mode`, `None` if there is none
+    fn random_for_mode(mode: ArenaMode) -> Option<Self> {
+        let kinds: Vec<_> = PowerUpKind::ALL
+            .into_iter()
+            .filter(|kind| kind.archetype().modes.contains(&mode))
+            .collect();
+        if kinds.is_empty() {
+            return None;
+        }
+        Some(kinds[rand::thread_rng().gen_range(0..kinds.len())])
+    }
+}
+
+pub struct PowerUpArchetype {
+    pub kind: PowerUpKind,
+    pub label: &'static str,
+    pub duration_ms: u64,
+    pub color: Color,
+    /// Modes where the pickup can drop
+    pub modes: &'static [ArenaMode],
+}
+
+pub const POWER_UP_ARCHETYPES: [PowerUpArchetype; 4] = [
+    PowerUpArchetype {
+        kind: PowerUpKind::SlowMotion,
+        label: "Slow motion",
+        duration_ms: 5000,
+        color: Color::Srgba(DEEP_SKY_BLUE),
+        modes: &[ArenaMode::Sword, ArenaMode::Shield],
+    },
+    PowerUpArchetype {
+        kind: PowerUpKind::WideBlade,
+        label: "Wide blade",
+        duration_ms: 8000,
+        color: Color::Srgba(LIME),
+        modes: &[ArenaMode::Sword],
+    },
+    PowerUpArchetype {
+        kind: PowerUpKind::AutoBlock,
+        label: "Auto-block",
+        duration_ms: 6000,
+        color: Color::Srgba(MEDIUM_PURPLE),
+        modes: &[ArenaMode::Shield],
+    },
+    PowerUpArchetype {
+        kind: PowerUpKind::ScoreDoubler,
+        label: "Score x2",
+        duration_ms: 10000,
+        color: Color::Srgba(GOLD),
+        modes: &[ArenaMode::Sword, ArenaMode::Shield],
+    },
+];
+
+/// Collected by slicing it in sword mode, or by blocking it in shield mode
+#[derive(Component, Debug, Reflect)]
+pub struct Pickup {
+    pub kind: PowerUpKind,
+    lifetime: Timer,
+}
+
+#[derive(Event, Debug)]
+pub struct SpawnPickup {
+    pub kind: PowerUpKind,
+    pub pos: Vec3,
+}
+
+#[derive(Event, Debug, Clone, Reflect)]
+pub struct PowerUpCollected {
+    pub kind: PowerUpKind,
+}
+
+/// Sent when a power-up becomes active, not when an active one is refreshed
+#[derive(Event, Debug, Clone, Reflect)]
+pub struct PowerUpStarted {
+    pub kind: PowerUpKind,
+}
+
+#[derive(Event, Debug, Clone, Reflect)]
+pub struct PowerUpEnded {
+    pub kind: PowerUpKind,
+}
+
+/// Remaining time of the active power-ups
+///
+/// Ticked with the real time, a slow-motion lasts as long as the others.
+#[derive(Resource, Debug, Default, Reflect)]
+pub struct ActivePowerUps {
+    timers: HashMap<PowerUpKind, Timer>,
+}
+impl ActivePowerUps {
+    pub fn is_active(&self, kind: PowerUpKind) -> bool {
+        self.timers.contains_key(&kind)
+    }
+
+    pub fn remaining(&self, kind: PowerUpKind) -> Option<Duration> {
+        self.timers.get(&kind).map(|timer| timer.remaining())
+    }
+}
+
+/// Run condition
+pub fn power_up_active(kind: PowerUpKind) -> impl Fn(Res<ActivePowerUps>) -> bool {
+    move |active_power_ups: Res<ActivePowerUps>| active_power_ups.is_active(kind)
+}
+
+#[derive(Resource)]
+struct PickupCachedData {
+    mesh: Handle<Mesh>,
+    materials: HashMap<PowerUpKind, Handle<StandardMaterial>>,
+}
+
+impl FromWorld for PickupCachedData {
+    fn from_world(world: &mut World) -> Self {
+        let mesh = world
+            .resource_mut::<Assets<Mesh>>()
+            .add(Sphere::new(PICKUP_RADIUS).mesh().ico(2).unwrap());
+        let mut materials = HashMap::new();
+        for archetype in POWER_UP_ARCHETYPES.iter() {
+            materials.insert(
+                archetype.kind,
+                world
+                    .resource_mut::<Assets<StandardMaterial>>()
+                    .add(StandardMaterial {
+                        base_color: archetype.color,
+                        emissive: archetype.color.to_linear() * 2.,
+                        ..default()
+                    }),
+            );
+        }
+        Self { mesh, materials }
+    }
+}
+
+/// Pickups only drop while fighting
+fn droppable_power_up(mode: ArenaMode) -> Option<PowerUpKind> {
+    match mode {
+        ArenaMode::Sword | ArenaMode::Shield => PowerUpKind::random_for_mode(mode),
+        _ => None,
+    }
+}
+
+fn drop_on_perfect_slice(trigger: Trigger<ScoreAction>, mut commands: Commands, cycle: Res<Cycle>) {
+    let score_action = trigger.event();
+    if score_action.action != ScoreActionType::Perfect
+        || !rand::thread_rng().gen_bool(PERFECT_SLICE_DROP_CHANCE)
+    {
+        return;
+    }
+    let Some(kind) = droppable_power_up(cycle.current_mode) else {
+        return;
+    };
+    commands.trigger(SpawnPickup {
+        kind,
+        pos: score_action.pos,
+    });
+}
+
+fn drop_on_broken_prop(trigger: Trigger<PropBroken>, mut commands: Commands, cycle: Res<Cycle>) {
+    if !rand::thread_rng().gen_bool(BROKEN_PROP_DROP_CHANCE) {
+        return;
+    }
+    // Props and debris outlive the game over
+    let Some(kind) = droppable_power_up(cycle.current_mode) else {
+        return;
+    };
+    commands.trigger(SpawnPickup {
+        kind,
+        pos: trigger.event().pos,
+    });
+}
+
+fn spawn_pickup(
+    trigger: Trigger<SpawnPickup>,
+    mut commands: Commands,
+    cycle: Res<Cycle>,
+    pickup_cached_data: Res<PickupCachedData>,
+    rapier_config: Res<RapierConfiguration>,
+    players_query: Query<&Transform, With<Player>>,
+) {
+    let spawn_info = trigger.event();
+    let pickup = commands
+        .spawn((
+            Name::new("Pickup"),
+            StateScoped(cycle.current_mode),
+            PbrBundle {
+                mesh: pickup_cached_data.mesh.clone(),
+                material: pickup_cached_data.materials[&spawn_info.kind].clone(),
+                ..default()
+            },
+            Pickup {
+                kind: spawn_info.kind,
+                lifetime: Timer::new(Duration::from_millis(PICKUP_LIFETIME_MS), TimerMode::Once),
+            },
+        ))
+        .id();
+
+    match cycle.current_mode {
+        ArenaMode::Sword => {
+            let pos = spawn_info.pos + PICKUP_FLOAT_HEIGHT * Vec3::Y;
+            let bob = Tween::new(
+                EaseFunction::SineInOut,
+                Duration::from_millis(PICKUP_BOB_PERIOD_MS),
+                TransformPositionLens {
+                    start: pos,
+                    end: pos + PICKUP_BOB_HEIGHT * Vec3::Y,
+                },
+            )
+            .with_repeat_count(RepeatCount::Infinite)
+            .with_repeat_strategy(RepeatStrategy::MirroredRepeat);
+            commands.entity(pickup).insert((
+                Transform::from_translation(pos),
+                Animator::new(bob),
+                Sliceable,
+                // Its fragments can't be sliced again
+                SliceDepth {
+                    depth: 0,
+                    max_depth: 1,
+                },
+            ));
+        }
+        ArenaMode::Shield => {
+            let Ok(player_transform) = players_query.get_single() else {
+                return;
+            };
+            let target = player_transform.translation + THROW_TARGET_TORSO_HEIGHT * Vec3::Y;
+            let linvel = ballistic_launch_velocity(
+                spawn_info.pos,
+                target,
+                Duration::from_millis(PICKUP_FLIGHT_TIME_MS).as_secs_f32(),
+                rapier_config.gravity,
+            );
+            commands.entity(pickup).insert((
+                Transform::from_translation(spawn_info.pos),
+                RigidBody::Dynamic,
+                Collider::ball(PICKUP_RADIUS),
+                ActiveCollisionTypes::default(),
+                ActiveEvents::COLLISION_EVENTS,
+                Velocity::linear(linvel),
+                // Only breaks when blocked
+                Breakable::damage_only(),
+            ));
+        }
+        ArenaMode::None | ArenaMode::GameOver => {
+            commands.entity(pickup).despawn_recursive();
+        }
+    }
+}
+
+fn despawn_expired_pickups(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut pickups_query: Query<(Entity, &mut Pickup)>,
+) {
+    for (entity, mut pickup) in pickups_query.iter_mut() {
+        pickup.lifetime.tick(time.delta());
+        if pickup.lifetime.finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+fn collect_sliced_pickup(
+    trigger: Trigger<SliceEvent>,
+    mut commands: Commands,
+    pickups_query: Query<&Pickup>,
+) {
+    let entity = trigger.event().entity;
+    let Ok(pickup) = pickups_query.get(entity) else {
+        return;
+    };
+    // The sliced entity is despawned when fragmented, stop tracking it as a pickup
+    commands
+        .entity(entity)
+        .remove::<(Pickup, Animator<Transform>)>();
+    commands.trigger(PowerUpCollected { kind: pickup.kind });
+}
+
+fn collect_blocked_pickups(
+    mut commands: Commands,
+    mut collision_events: EventReader<CollisionEvent>,
+    shield_query: Query<(), With<Shield>>,
+    pickups_query: Query<(&Pickup, &Transform, &Velocity)>,
+) {
+    for collision_event in collision_events.read() {
+        let CollisionEvent::Started(e1, e2, _flags) = collision_event else {
+            continue;
+        };
+        let pickup_entity = if shield_query.contains(*e1) {
+            *e2
+        } else if shield_query.contains(*e2) {
+            *e1
+        } else {
+            continue;
+        };
+        let Ok((pickup, transform, velocity)) = pickups_query.get(pickup_entity) else {
+            continue;
+        };
+        commands.entity(pickup_entity).remove::<Pickup>();
+        commands.trigger(DamageBreakable {
+            entity: pickup_entity,
+            damage: BREAK_DAMAGE,
+            impact_point: transform.translation,
+            impact_velocity: -velocity.linvel,
+        });
+        commands.trigger(PowerUpCollected { kind: pickup.kind });
+    }
+}
+
+/// Collecting an active power-up restarts its timer
+fn activate_power_up(
+    trigger: Trigger<PowerUpCollected>,
+    mut commands: Commands,
+    mut active_power_ups: ResMut<ActivePowerUps>,
+) {
+    let kind = trigger.event().kind;
+    let duration = Duration::from_millis(kind.archetype().duration_ms);
+    if let Some(timer) = active_power_ups.timers.get_mut(&kind) {
+        timer.reset();
+        return;
+    }
+    active_power_ups
+        .timers
+        .insert(kind, Timer::new(duration, TimerMode::Once));
+    commands.trigger(PowerUpStarted { kind });
+}
+
+fn tick_power_ups(
+    mut commands: Commands,
+    real_time: Res<Time<Real>>,
+    mut active_power_ups: ResMut<ActivePowerUps>,
+) {
+    for timer in active_power_ups.timers.values_mut() {
+        timer.tick(real_time.delta());
+    }
+    active_power_ups.timers.retain(|kind, timer| {
+        if timer.finished() {
+            commands.trigger(PowerUpEnded { kind: *kind });
+        }
+        !timer.finished()
+    });
+}
+
+fn end_all_power_ups(mut commands: Commands, mut active_power_ups: ResMut<ActivePowerUps>) {
+    for (kind, _) in active_power_ups.timers.drain() {
+        commands.trigger(PowerUpEnded { kind });
+    }
+}
+
+fn apply_power_up_effect(
+    trigger: Trigger<PowerUpStarted>,
+    mut time_scale: ResMut<TimeScale>,
+    mut slice_settings: ResMut<SliceSettings>,
+    mut score_modifiers: ResMut<ScoreModifiers>,
+) {
+    match trigger.event().kind {
+        PowerUpKind::SlowMotion => {
+            // Lasts until the power-up ends
+            time_scale.request(TimeScaleSource::PowerUp, SLOW_MOTION_TIME_SCALE, None)
+        }
+        PowerUpKind::WideBlade => slice_settings.blade_half_width = WIDE_BLADE_HALF_WIDTH,
+        PowerUpKind::ScoreDoubler => score_modifiers.positive_multiplier = SCORE_DOUBLER_MULTIPLIER,
+        // Drives the shield with a run condition, see shield::player_control
+        PowerUpKind::AutoBlock => (),
+    }
+}
+
+fn remove_power_up_effect(
+    trigger: Trigger<PowerUpEnded>,
+    mut time_scale: ResMut<TimeScale>,
+    mut slice_settings: ResMut<SliceSettings>,
+    mut score_modifiers: ResMut<ScoreModifiers>,
+) {
+    match trigger.event().kind {
+        PowerUpKind::SlowMotion => time_scale.release(TimeScaleSource::PowerUp),
+        PowerUpKind::WideBlade => slice_settings.blade_half_width = 0.,
+        PowerUpKind::ScoreDoubler => score_modifiers.positive_multiplier = 1.,
+        PowerUpKind::AutoBlock => (),
+    }
+}
+
+#[derive(Component)]
+pub struct PowerUpsHud;
+
+#[derive(Component)]
+pub struct PowerUpHudEntry(PowerUpKind);
+
+#[derive(Component)]
+pub struct PowerUpTimerText(PowerUpKind);
+
+fn setup_power_ups_ui(mut commands: Commands) {
+    commands
+        .top_right_ui_root()
+        .insert((PowerUpsHud, StateScoped(Screen::Playing)));
+}
+
+fn add_power_up_hud_entry(
+    trigger: Trigger<PowerUpStarted>,
+    mut commands: Commands,
+    font_handles: Res<HandleMap<FontKey>>,
+    huds_query: Query<Entity, With<PowerUpsHud>>,
+) {
+    let Ok(hud) = huds_query.get_single() else {
+        return;
+    };
+    let kind = trigger.event().kind;
+    let font = font_handles.get(&DEFAULT_FONT_KEY).unwrap().clone_weak();
+    commands.entity(hud).with_children(|children| {
+        children
+            .dynamic_label_with_marker(
+                format!("{}: ", kind.archetype().label),
+                "",
+                PowerUpTimerText(kind),
+                font,
+            )
+            .insert(PowerUpHudEntry(kind));
+    });
+}
+
+fn remove_power_up_hud_entry(
+    trigger: Trigger<PowerUpEnded>,
+    mut commands: Commands,
+    entries_query: Query<(Entity, &PowerUpHudEntry)>,
+) {
+    for (entity, entry) in entries_query.iter() {
+        if entry.0 == trigger.event().kind {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+fn update_power_ups_ui(
+    active_power_ups: Res<ActivePowerUps>,
+    mut timer_texts_query: Query<(&mut Text, &PowerUpTimerText)>,
+) {
+    for (mut text, timer_text) in timer_texts_query.iter_mut() {
+        let Some(remaining) = active_power_ups.remaining(timer_text.0) else {
+            continue;
+        };
+        text.sections[1].value = format!("{:.1}s", remaining.as_secs_f32());
+    }
+}
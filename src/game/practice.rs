@@ -0,0 +1,316 @@
+//! Sandbox on a single mode, with no weapon cycle and no game over.
+//!
+//! The difficulty stays at its initial value and the sliders override the spawn rates of the mode instead.
+
+use std::time::Duration;
+
+use bevy::{
+    app::{App, Update},
+    prelude::{
+        in_state, resource_exists, BuildChildren, Changed, Commands, Component, DetectChanges,
+        IntoSystemConfigs, OnEnter, OnExit, ParamSet, Query, Res, ResMut, Resource, StateScoped,
+        Trigger, With,
+    },
+    reflect::Reflect,
+    text::Text,
+    time::{Timer, TimerMode},
+};
+
+use crate::{screen::Screen, ui::prelude::*, AppSet};
+
+use super::{
+    arena::ArenaMode,
+    assets::{FontKey, HandleMap, DEFAULT_FONT_KEY},
+    cycle::{Cycle, NEXT_WEAPON_CYCLE_INTERVAL_MAX},
+    score::{ScoreAction, ScoreActionType},
+    shield::throwers::ThrowersData,
+    sword::dummies::DummiesModeData,
+};
+
+/// Ranges covered by the sliders
+pub const PRACTICE_SPAWN_INTERVAL_RANGE_MS: (u64, u64) = (300, 3000);
+pub const PRACTICE_DUMMY_LIFETIME_RANGE_MS: (u64, u64) = (800, 5000);
+/// The fastest throws have the shortest flight
+pub const PRACTICE_JUG_FLIGHT_TIME_RANGE_MS: (u64, u64) = (2500, 600);
+/// The spawn delays are randomly picked around the set interval, within this fraction
+pub const PRACTICE_SPAWN_INTERVAL_SPREAD: f32 = 0.25;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Practice>();
+    app.register_type::<PracticeSetting>();
+
+    app.add_systems(
+        OnEnter(Screen::Playing),
+        setup_practice_ui.run_if(resource_exists::<Practice>),
+    );
+    app.add_systems(OnExit(Screen::Playing), end_practice);
+    app.add_systems(
+        Update,
+        (
+            read_practice_sliders.in_set(AppSet::RecordInput),
+            (apply_practice_settings, update_practice_ui).in_set(AppSet::Update),
+        )
+            .run_if(in_state(Screen::Playing).and_then(resource_exists::<Practice>)),
+    );
+
+    app.observe(record_practice_stats);
+}
+
+/// Present while practicing, insert it before entering [`Screen::Playing`]
+#[derive(Resource, Debug, Reflect)]
+pub struct Practice {
+    pub mode: ArenaMode,
+    pub spawn_interval_ms: u64,
+    pub dummy_lifetime_ms: u64,
+    pub jug_flight_time_ms: u64,
+    pub stats: PracticeStats,
+}
+impl Practice {
+    pub fn new(mode: ArenaMode) -> Self {
+        Self {
+            mode,
+            spawn_interval_ms: 1000,
+            dummy_lifetime_ms: 2200,
+            jug_flight_time_ms: 1500,
+            stats: PracticeStats::default(),
+        }
+    }
+
+    /// Stays on the practiced mode
+    pub fn cycle(&self) -> Cycle {
+        let mut next_mode_timer = Timer::new(
+            Duration::from_millis(NEXT_WEAPON_CYCLE_INTERVAL_MAX),
+            TimerMode::Repeating,
+        );
+        next_mode_timer.pause();
+        Cycle {
+            current_mode: self.mode,
+            next_mode: self.mode,
+            next_mode_timer,
+            completed_cycles: 0,
+        }
+    }
+
+    fn spawn_interval_range_ms(&self) -> (u64, u64) {
+        let spread = (self.spawn_interval_ms as f32 * PRACTICE_SPAWN_INTERVAL_SPREAD) as u64;
+        (
+            self.spawn_interval_ms - spread,
+            self.spawn_interval_ms + spread.max(1),
+        )
+    }
+}
+
+#[derive(Debug, Default, Clone, Reflect)]
+pub struct PracticeStats {
+    /// Slices and blocks
+    pub successes: u32,
+    pub perfects: u32,
+    /// Misses, hits taken and sliced decoys
+    pub failures: u32,
+}
+impl PracticeStats {
+    pub fn accuracy(&self) -> Option<f32> {
+        let total = self.successes + self.failures;
+        (total > 0).then(|| self.successes as f32 / total as f32)
+    }
+
+    pub fn perfect_ratio(&self) -> Option<f32> {
+        (self.successes > 0).then(|| self.perfects as f32 / self.successes as f32)
+    }
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum PracticeSetting {
+    SpawnInterval,
+    DummyLifetime,
+    ThrowSpeed,
+}
+impl PracticeSetting {
+    fn label(&self) -> &'static str {
+        match self {
+            PracticeSetting::SpawnInterval => "Spawn interval: ",
+            PracticeSetting::DummyLifetime => "Dummy lifetime: ",
+            PracticeSetting::ThrowSpeed => "Throw flight: ",
+        }
+    }
+
+    fn range_ms(&self) -> (u64, u64) {
+        match self {
+            PracticeSetting::SpawnInterval => PRACTICE_SPAWN_INTERVAL_RANGE_MS,
+            PracticeSetting::DummyLifetime => PRACTICE_DUMMY_LIFETIME_RANGE_MS,
+            PracticeSetting::ThrowSpeed => PRACTICE_JUG_FLIGHT_TIME_RANGE_MS,
+        }
+    }
+
+    fn value_ms(&self, practice: &Practice) -> u64 {
+        match self {
+            PracticeSetting::SpawnInterval => practice.spawn_interval_ms,
+            PracticeSetting::DummyLifetime => practice.dummy_lifetime_ms,
+            PracticeSetting::ThrowSpeed => practice.jug_flight_time_ms,
+        }
+    }
+
+    fn set_value_ms(&self, practice: &mut Practice, value_ms: u64) {
+        match self {
+            PracticeSetting::SpawnInterval => practice.spawn_interval_ms = value_ms,
+            PracticeSetting::DummyLifetime => practice.dummy_lifetime_ms = value_ms,
+            PracticeSetting::ThrowSpeed => practice.jug_flight_time_ms = value_ms,
+        }
+    }
+
+    fn to_slider(&self, value_ms: u64) -> f32 {
+        let (from, to) = self.range_ms();
+        ((value_ms as f32 - from as f32) / (to as f32 - from as f32)).clamp(0., 1.)
+    }
+
+    fn from_slider(&self, slider: f32) -> u64 {
+        let (from, to) = self.range_ms();
+        (from as f32 + (to as f32 - from as f32) * slider) as u64
+    }
+
+    fn modes(&self) -> &'static [ArenaMode] {
+        match self {
+            PracticeSetting::SpawnInterval => &[ArenaMode::Sword, ArenaMode::Shield],
+            PracticeSetting::DummyLifetime => &[ArenaMode::Sword],
+            PracticeSetting::ThrowSpeed => &[ArenaMode::Shield],
+        }
+    }
+}
+
+/// Text showing the value of a [`PracticeSetting`]
+#[derive(Component, Debug)]
+pub struct PracticeSettingText(pub PracticeSetting);
+
+#[derive(Component, Debug)]
+pub struct PracticeAccuracyText;
+
+#[derive(Component, Debug)]
+pub struct PracticePerfectRatioText;
+
+fn format_seconds(value_ms: u64) -> String {
+    format!("{:.1}s", value_ms as f32 / 1000.)
+}
+
+fn format_ratio(ratio: Option<f32>) -> String {
+    ratio.map_or("-".to_string(), |ratio| format!("{:.0}%", ratio * 100.))
+}
+
+fn setup_practice_ui(
+    mut commands: Commands,
+    practice: Res<Practice>,
+    font_handles: Res<HandleMap<FontKey>>,
+) {
+    let font = font_handles.get(&DEFAULT_FONT_KEY).unwrap().clone();
+    commands
+        .top_left_ui_root()
+        .insert(StateScoped(Screen::Playing))
+        .with_children(|children| {
+            for setting in [
+                PracticeSetting::SpawnInterval,
+                PracticeSetting::DummyLifetime,
+                PracticeSetting::ThrowSpeed,
+            ] {
+                if !setting.modes().contains(&practice.mode) {
+                    continue;
+                }
+                let value_ms = setting.value_ms(&practice);
+                children.dynamic_label_with_marker(
+                    setting.label(),
+                    format_seconds(value_ms),
+                    PracticeSettingText(setting),
+                    font.clone_weak(),
+                );
+                children.slider_with_marker(setting.to_slider(value_ms), setting);
+            }
+            children.dynamic_label_with_marker(
+                "Accuracy: ",
+                "-",
+                PracticeAccuracyText,
+                font.clone_weak(),
+            );
+            children.dynamic_label_with_marker(
+                "Perfect: ",
+                "-",
+                PracticePerfectRatioText,
+                font.clone_weak(),
+            );
+        });
+}
+
+fn end_practice(mut commands: Commands) {
+    commands.remove_resource::<Practice>();
+}
+
+fn read_practice_sliders(
+    mut practice: ResMut<Practice>,
+    sliders_query: Query<(&Slider, &PracticeSetting), Changed<Slider>>,
+) {
+    for (slider, setting) in sliders_query.iter() {
+        let value_ms = setting.from_slider(slider.value);
+        if setting.value_ms(&practice) != value_ms {
+            setting.set_value_ms(&mut practice, value_ms);
+        }
+    }
+}
+
+/// The mode data is recreated each time the mode is entered, so the overrides are kept in sync continuously
+fn apply_practice_settings(
+    practice: Res<Practice>,
+    dummies_mode: Option<ResMut<DummiesModeData>>,
+    throwers: Option<ResMut<ThrowersData>>,
+) {
+    let spawn_interval_range_ms = practice.spawn_interval_range_ms();
+    if let Some(mut dummies_mode) = dummies_mode {
+        if dummies_mode.spawn_interval_ms != spawn_interval_range_ms
+            || dummies_mode.dummy_lifetime_ms != Some(practice.dummy_lifetime_ms)
+        {
+            dummies_mode.spawn_interval_ms = spawn_interval_range_ms;
+            dummies_mode.dummy_lifetime_ms = Some(practice.dummy_lifetime_ms);
+        }
+    }
+    if let Some(mut throwers) = throwers {
+        if throwers.throw_interval_ms != Some(spawn_interval_range_ms)
+            || throwers.jug_flight_time_ms != Some(practice.jug_flight_time_ms)
+        {
+            throwers.throw_interval_ms = Some(spawn_interval_range_ms);
+            throwers.jug_flight_time_ms = Some(practice.jug_flight_time_ms);
+        }
+    }
+}
+
+fn update_practice_ui(
+    practice: Res<Practice>,
+    mut texts_query: ParamSet<(
+        Query<(&mut Text, &PracticeSettingText)>,
+        Query<&mut Text, With<PracticeAccuracyText>>,
+        Query<&mut Text, With<PracticePerfectRatioText>>,
+    )>,
+) {
+    if !practice.is_changed() {
+        return;
+    }
+    for (mut text, setting_text) in texts_query.p0().iter_mut() {
+        text.sections[1].value = format_seconds(setting_text.0.value_ms(&practice));
+    }
+    for mut text in texts_query.p1().iter_mut() {
+        text.sections[1].value = format_ratio(practice.stats.accuracy());
+    }
+    for mut text in texts_query.p2().iter_mut() {
+        text.sections[1].value = format_ratio(practice.stats.perfect_ratio());
+    }
+}
+
+fn record_practice_stats(trigger: Trigger<ScoreAction>, practice: Option<ResMut<Practice>>) {
+    let Some(mut practice) = practice else {
+        return;
+    };
+    match trigger.event().action {
+        ScoreActionType::Bad => practice.stats.failures += 1,
+        ScoreActionType::Good => practice.stats.successes += 1,
+        ScoreActionType::Perfect => {
+            practice.stats.successes += 1;
+            practice.stats.perfects += 1;
+        }
+        ScoreActionType::Juggle | ScoreActionType::MultiSlice(_) | ScoreActionType::Prop => (),
+    }
+}
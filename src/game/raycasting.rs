@@ -0,0 +1,86 @@
+//! Cursor raycasts filtered by purpose.
+//!
+//! Each purpose only sees its own targets: slicing ignores the fragments, shards and vfx passing in front of a dummy,
+//! and the shield aims on a horizontal plane instead of whatever mesh is under the cursor.
+
+use bevy::{
+    app::App,
+    ecs::system::SystemParam,
+    math::{primitives::InfinitePlane3d, Ray3d, Vec3},
+    prelude::{default, Entity, Query, ReflectResource, Res, Resource, With},
+    reflect::Reflect,
+};
+use bevy_mod_raycast::prelude::{Raycast, RaycastSettings};
+
+use super::sword::slicing::Sliceable;
+
+/// Default height of the shield aim plane, above the player's feet
+pub const SHIELD_AIM_PLANE_HEIGHT: f32 = 3.;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<RaycastFilters>();
+    app.init_resource::<RaycastFilters>();
+}
+
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct RaycastFilters {
+    /// Stop at the first target hit instead of testing every mesh under the ray
+    pub early_exit: bool,
+    /// Height of the shield aim plane, relative to the player
+    pub aim_plane_height: f32,
+}
+impl Default for RaycastFilters {
+    fn default() -> Self {
+        Self {
+            early_exit: true,
+            aim_plane_height: SHIELD_AIM_PLANE_HEIGHT,
+        }
+    }
+}
+
+#[derive(SystemParam)]
+pub struct FilteredRaycast<'w, 's> {
+    raycast: Raycast<'w, 's>,
+    filters: Res<'w, RaycastFilters>,
+    sliceables_query: Query<'w, 's, (), With<Sliceable>>,
+}
+
+impl<'w, 's> FilteredRaycast<'w, 's> {
+    /// Closest [`Sliceable`] under the ray, anything else is transparent
+    pub fn cast_sliceable(&mut self, ray: Ray3d) -> Option<(Entity, Vec3)> {
+        let sliceables_query = &self.sliceables_query;
+        let is_sliceable = |entity| sliceables_query.contains(entity);
+        first_hit(
+            &mut self.raycast,
+            ray,
+            &is_sliceable,
+            self.filters.early_exit,
+        )
+    }
+
+    /// Where the ray crosses the aim plane of a player standing at `player_pos`, no mesh is tested
+    pub fn aim_point(&self, ray: Ray3d, player_pos: Vec3) -> Option<Vec3> {
+        let plane_origin = player_pos + Vec3::Y * self.filters.aim_plane_height;
+        ray.intersect_plane(plane_origin, InfinitePlane3d::new(Vec3::Y))
+            .map(|distance| ray.get_point(distance))
+    }
+}
+
+fn first_hit(
+    raycast: &mut Raycast,
+    ray: Ray3d,
+    filter: &dyn Fn(Entity) -> bool,
+    early_exit: bool,
+) -> Option<(Entity, Vec3)> {
+    let early_exit_test = |_| early_exit;
+    let settings = RaycastSettings {
+        filter,
+        early_exit_test: &early_exit_test,
+        ..default()
+    };
+    raycast
+        .cast_ray(ray, &settings)
+        .first()
+        .map(|(entity, hit)| (*entity, hit.position()))
+}
@@ -5,7 +5,7 @@ use crate::ui::prelude::*;
 use bevy::{
     app::{App, Update},
     color::{
-        palettes::css::{LIGHT_BLUE, LIGHT_GREEN, RED},
+        palettes::css::{GOLD, LIGHT_BLUE, LIGHT_GREEN, ORANGE, RED},
         Alpha, Color,
     },
     math::Vec3,
@@ -28,11 +28,21 @@ use super::{
     arena::ArenaMode,
     assets::{FontKey, HandleMap, DEFAULT_FONT_KEY},
     cycle::Cycle,
+    game_mode::GameMode,
+    practice::Practice,
+    tutorial::Tutorial,
 };
 
 pub const DEFAULT_BAD_ACTION_SCORE: f32 = -10.;
 pub const DEFAULT_GOOD_ACTION_SCORE: f32 = 10.;
 pub const DEFAULT_PERFECT_ACTION_SCORE: f32 = 15.;
+pub const DEFAULT_JUGGLE_ACTION_SCORE: f32 = 20.;
+/// For each target after the first one
+pub const DEFAULT_MULTI_SLICE_ACTION_SCORE: f32 = 10.;
+pub const DEFAULT_PROP_ACTION_SCORE: f32 = 15.;
+
+/// Losing points while the score is below this gives a last chance before the game over
+pub const LAST_CHANCE_SCORE_THRESHOLD: i32 = 15;
 
 pub const INITIAL_DIFFICULTY_FACTOR: f32 = 1.;
 pub const MAX_DIFFICULTY_FACTOR: f32 = 2.;
@@ -42,6 +52,9 @@ pub const SCORE_BILLBOARDS_TEXT_DURATION_MS: u64 = 1850;
 pub const SCORE_BILLBOARD_TEXT_COLOR_BAD: Color = Color::Srgba(RED);
 pub const SCORE_BILLBOARD_TEXT_COLOR_GOOD: Color = Color::Srgba(LIGHT_BLUE);
 pub const SCORE_BILLBOARD_TEXT_COLOR_PERFECT: Color = Color::Srgba(LIGHT_GREEN);
+pub const SCORE_BILLBOARD_TEXT_COLOR_JUGGLE: Color = Color::Srgba(GOLD);
+pub const SCORE_BILLBOARD_TEXT_COLOR_MULTI_SLICE: Color = Color::Srgba(GOLD);
+pub const SCORE_BILLBOARD_TEXT_COLOR_PROP: Color = Color::Srgba(ORANGE);
 pub const SCORE_BILLBOARDS_TEXT_SIZE: f32 = 66.0;
 pub const SCORE_BILLBOARDS_SCALE: f32 = 0.03;
 pub const SCORE_BILLBOARDS_FROM_DELTA: f32 = 4.;
@@ -52,6 +65,30 @@ pub struct Score {
     highscore: u32,
     current: i32,
 }
+impl Score {
+    pub fn current(&self) -> i32 {
+        self.current
+    }
+
+    /// Best score reached during the run
+    pub fn highscore(&self) -> u32 {
+        self.highscore
+    }
+}
+
+/// Can be changed by power-ups
+#[derive(Resource, Reflect, Clone)]
+pub struct ScoreModifiers {
+    /// Applied to the points earned, not to the points lost
+    pub positive_multiplier: f32,
+}
+impl Default for ScoreModifiers {
+    fn default() -> Self {
+        Self {
+            positive_multiplier: 1.,
+        }
+    }
+}
 
 #[derive(Resource, Reflect, Clone)]
 pub struct Difficulty {
@@ -73,6 +110,8 @@ impl Difficulty {
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<Score>();
     app.register_type::<Difficulty>();
+    app.register_type::<ScoreModifiers>();
+    app.init_resource::<ScoreModifiers>();
 
     app.add_systems(OnEnter(Screen::Playing), (setup_score_ui, setup_score));
 
@@ -87,13 +126,20 @@ pub(super) fn plugin(app: &mut App) {
     app.observe(handle_score_actions);
     app.observe(update_score_ui);
     app.observe(detect_game_over);
+    app.observe(end_run);
 }
 
-#[derive(Reflect, PartialEq, Eq, Clone)]
+#[derive(Reflect, PartialEq, Eq, Clone, Copy, Debug)]
 pub enum ScoreActionType {
     Bad,
     Good,
     Perfect,
+    /// Cut a fragment before it landed
+    Juggle,
+    /// Sliced this many targets with a single stroke
+    MultiSlice(u32),
+    /// Broke an arena prop
+    Prop,
 }
 impl ScoreActionType {
     fn to_properties(&self) -> (f32, Color, &str) {
@@ -113,6 +159,21 @@ impl ScoreActionType {
                 SCORE_BILLBOARD_TEXT_COLOR_PERFECT,
                 "Perfect",
             ),
+            ScoreActionType::Juggle => (
+                DEFAULT_JUGGLE_ACTION_SCORE,
+                SCORE_BILLBOARD_TEXT_COLOR_JUGGLE,
+                "Juggle",
+            ),
+            ScoreActionType::MultiSlice(count) => (
+                DEFAULT_MULTI_SLICE_ACTION_SCORE * count.saturating_sub(1) as f32,
+                SCORE_BILLBOARD_TEXT_COLOR_MULTI_SLICE,
+                "Multi-slice",
+            ),
+            ScoreActionType::Prop => (
+                DEFAULT_PROP_ACTION_SCORE,
+                SCORE_BILLBOARD_TEXT_COLOR_PROP,
+                "Smashed",
+            ),
         }
     }
 }
@@ -130,6 +191,7 @@ pub fn handle_score_actions(
     cycle: Res<Cycle>,
     mut score: ResMut<Score>,
     difficulty: ResMut<Difficulty>,
+    score_modifiers: Res<ScoreModifiers>,
     font_handles: Res<HandleMap<FontKey>>,
     camera_query: Query<&Transform, With<Camera>>,
 ) {
@@ -141,7 +203,8 @@ pub fn handle_score_actions(
         score_action.action.to_properties();
     let difficulty_factor = difficulty.difficulty_factor();
     let (rounded_action_value, action_text) = if score_action_raw_value > 0. {
-        let value = (score_action_raw_value / difficulty_factor) as i32;
+        let value = (score_action_raw_value * score_modifiers.positive_multiplier
+            / difficulty_factor) as i32;
         (value, format!("{} (+{})", action_text, value))
     } else {
         let value = (score_action_raw_value * difficulty_factor) as i32;
@@ -152,6 +215,9 @@ pub fn handle_score_actions(
     if score.current > 0 && score.current as u32 > score.highscore {
         score.highscore = score.current as u32;
     }
+    if rounded_action_value < 0 && (0..LAST_CHANCE_SCORE_THRESHOLD).contains(&score.current) {
+        commands.trigger(LastChance);
+    }
 
     let Some(font) = font_handles.get(&DEFAULT_FONT_KEY) else {
         return;
@@ -230,6 +296,14 @@ fn despawn_score_billboards(
 #[derive(Event, Clone, Reflect)]
 pub struct ScoreUpdate;
 
+/// The score dropped close to a game over
+#[derive(Event, Clone, Reflect)]
+pub struct LastChance;
+
+/// Ends the run with a game over, whatever the score
+#[derive(Event, Clone, Reflect)]
+pub struct EndRun;
+
 pub fn setup_score(mut commands: Commands) {
     commands.insert_resource(Score {
         highscore: 0,
@@ -279,15 +353,29 @@ pub fn setup_score_ui(mut commands: Commands, font_handles: Res<HandleMap<FontKe
 
 pub fn detect_game_over(
     _trigger: Trigger<ScoreUpdate>,
+    mut commands: Commands,
     score: Res<Score>,
+    tutorial: Option<Res<Tutorial>>,
+    practice: Option<Res<Practice>>,
+    game_mode: Option<Res<GameMode>>,
+) {
+    let ends_below_zero = game_mode.map_or(true, |game_mode| game_mode.kind.ends_below_zero());
+    if score.current < 0 && ends_below_zero && tutorial.is_none() && practice.is_none() {
+        commands.trigger(EndRun);
+    }
+}
+
+pub fn end_run(
+    _trigger: Trigger<EndRun>,
     mut cycle: ResMut<Cycle>,
     mut next_mode: ResMut<NextState<ArenaMode>>,
 ) {
-    if score.current < 0 {
-        next_mode.set(ArenaMode::GameOver);
-        cycle.current_mode = ArenaMode::GameOver;
-        cycle.next_mode_timer.pause();
+    if cycle.current_mode == ArenaMode::GameOver {
+        return;
     }
+    next_mode.set(ArenaMode::GameOver);
+    cycle.current_mode = ArenaMode::GameOver;
+    cycle.next_mode_timer.pause();
 }
 
 pub fn update_score_ui(
@@ -322,6 +410,7 @@ pub fn update_score_ui(
 pub fn update_difficulty(
     time: Res<Time>,
     cycle: Res<Cycle>,
+    practice: Option<Res<Practice>>,
     mut difficulty: ResMut<Difficulty>,
     mut timer_text_query: Query<&mut Text, With<DifficultyTimerText>>,
 ) {
@@ -329,6 +418,10 @@ pub fn update_difficulty(
     if cycle.current_mode == ArenaMode::GameOver {
         return;
     }
+    // The practice sliders replace the difficulty
+    if practice.is_some() {
+        return;
+    }
 
     difficulty.time_elapsed_s += time.delta_seconds();
 
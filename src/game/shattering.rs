@@ -1,35 +1,59 @@
-use std::time::Duration;
+use std::{f32::consts::TAU, time::Duration};
 
 use bevy::{
     app::{App, Update},
-    asset::{Assets, Handle},
+    asset::{AssetId, Assets, Handle},
     core::Name,
-    math::Vec3,
+    math::{Quat, Vec3, Vec3A},
     pbr::{PbrBundle, StandardMaterial},
     prelude::{
-        Commands, Component, DespawnRecursiveExt, Entity, Event, IntoSystemConfigs, Mesh, Query,
-        Res, ResMut, StateScoped, Transform, Trigger,
+        BuildChildren, Commands, Component, DespawnRecursiveExt, Entity, Event, IntoSystemConfigs,
+        Mesh, Query, Res, ResMut, Resource, Transform, Trigger, Without,
     },
     reflect::Reflect,
-    time::{Time, Timer, TimerMode},
-    utils::default,
+    tasks::{block_on, futures_lite::future, AsyncComputeTaskPool, Task},
+    utils::{default, HashMap},
+};
+use bevy_ghx_destruction::{
+    slicing::slicing::{slice_bevy_mesh, slice_bevy_mesh_iterative},
+    types::Plane,
 };
-use bevy_ghx_destruction::slicing::slicing::slice_bevy_mesh_iterative;
 use bevy_rapier3d::prelude::{
     ActiveCollisionTypes, Collider, ColliderMassProperties, ComputedColliderShape, ExternalImpulse,
     Friction, Restitution, RigidBody,
 };
-use rand::Rng;
+use rand::{seq::SliceRandom, Rng};
+
+use crate::AppSet;
 
-use crate::{screen::Screen, AppSet};
+use super::{
+    breakable::{Breakable, FractureMode},
+    debris::{spawn_debris_group, DebrisBudget, DebrisExpiry, DebrisPiece},
+    spawn::jug::Jug,
+    sword::slicing::{Sliceable, SlicedFragment},
+};
 
 pub const SHARDS_DESPAWN_DELAY_MS: u64 = 3000;
 pub const SHATTER_ITERATION_COUNT: u32 = 6;
-pub const FIXED_MIN_SHARD_MASS: f32 = 0.04;
-pub const FIXED_MAX_SHARD_MASS: f32 = 0.08;
+/// Mass of a shard per unit of volume
+pub const SHARD_DENSITY: f32 = 0.6;
+pub const MIN_SHARD_MASS: f32 = 0.01;
+/// Shards closer to the impact than this factor of the mesh size are broken once more
+pub const IMPACT_FRACTURE_RADIUS_FACTOR: f32 = 0.5;
+/// Part of the impactor velocity given to the shards
+pub const SHARD_IMPACT_VELOCITY_TRANSFER: f32 = 0.6;
+/// Speed at which the shards burst away from the impact point
+pub const SHARD_BURST_SPEED: f32 = 2.5;
+/// Distance from the impact at which the impulses are halved
+pub const SHARD_IMPULSE_FALLOFF_DISTANCE: f32 = 0.5;
+pub const SHARD_MAX_TORQUE_IMPULSE: f32 = 0.02;
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_systems(Update, ((despawn_shards).in_set(AppSet::Update),));
+    app.register_type::<ShatterSettings>();
+    app.init_resource::<ShatterSettings>();
+    app.init_resource::<FractureLibraries>();
+
+    app.add_systems(Update, apply_shatter_results.in_set(AppSet::Update));
 
     app.observe(shatter_entity);
 }
@@ -37,107 +61,700 @@ pub(super) fn plugin(app: &mut App) {
 #[derive(Event, Debug, Clone, Reflect)]
 pub struct ShatterEntity {
     pub entity: Entity,
-    pub impulse: Vec3,
+    /// Where the entity was hit, in world space
+    pub impact_point: Vec3,
+    /// Velocity of the impactor relative to the shattered entity
+    pub impact_velocity: Vec3,
+}
+
+#[derive(Resource, Debug, Reflect)]
+pub struct ShatterSettings {
+    /// Fracture the meshes that were not prefractured on a background thread
+    pub asynchronous: bool,
+}
+impl Default for ShatterSettings {
+    fn default() -> Self {
+        Self { asynchronous: true }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PrefracturedShard {
+    pub mesh: Handle<Mesh>,
+    pub collider: Collider,
+    /// Center of the shard, in the local space of the fractured mesh
+    pub center: Vec3,
+    pub volume: f32,
+}
+
+/// A cut of a [`FracturePattern`], in the mesh local space. Offsets are relative to the mesh bounds.
+#[derive(Debug, Clone, Copy)]
+pub struct FractureCut {
+    pub offset: Vec3,
+    pub normal: Vec3,
+}
+
+/// Authored set of cuts, for an impact travelling along -Z
+#[derive(Debug, Clone, Copy)]
+pub struct FracturePattern {
+    pub name: &'static str,
+    pub cuts: &'static [FractureCut],
+}
+
+pub const FRACTURE_PATTERNS: [FracturePattern; 3] = [
+    FracturePattern {
+        name: "Radial",
+        cuts: &[
+            FractureCut {
+                offset: Vec3::ZERO,
+                normal: Vec3::X,
+            },
+            FractureCut {
+                offset: Vec3::ZERO,
+                normal: Vec3::new(0.5, 0.866, 0.),
+            },
+            FractureCut {
+                offset: Vec3::ZERO,
+                normal: Vec3::new(-0.5, 0.866, 0.),
+            },
+            FractureCut {
+                offset: Vec3::new(0., 0., 0.4),
+                normal: Vec3::Z,
+            },
+        ],
+    },
+    FracturePattern {
+        name: "Layers",
+        cuts: &[
+            FractureCut {
+                offset: Vec3::new(0., 0., 0.5),
+                normal: Vec3::Z,
+            },
+            FractureCut {
+                offset: Vec3::new(0., 0., 0.1),
+                normal: Vec3::new(0., 0.3, 1.),
+            },
+            FractureCut {
+                offset: Vec3::new(0., 0., -0.4),
+                normal: Vec3::new(0., -0.3, 1.),
+            },
+            FractureCut {
+                offset: Vec3::ZERO,
+                normal: Vec3::Y,
+            },
+            FractureCut {
+                offset: Vec3::new(0., 0., 0.5),
+                normal: Vec3::X,
+            },
+        ],
+    },
+    FracturePattern {
+        name: "Crater",
+        cuts: &[
+            FractureCut {
+                offset: Vec3::new(0., 0., 0.6),
+                normal: Vec3::new(0.4, 0., 1.),
+            },
+            FractureCut {
+                offset: Vec3::new(0., 0., 0.6),
+                normal: Vec3::new(-0.4, 0., 1.),
+            },
+            FractureCut {
+                offset: Vec3::new(0., 0.3, 0.),
+                normal: Vec3::Y,
+            },
+            FractureCut {
+                offset: Vec3::new(0., -0.3, 0.),
+                normal: Vec3::Y,
+            },
+            FractureCut {
+                offset: Vec3::ZERO,
+                normal: Vec3::X,
+            },
+        ],
+    },
+];
+/// Each pattern is precomputed for this many orientations around the local Y axis
+pub const FRACTURE_PATTERN_ORIENTATIONS: u32 = 6;
+/// Variants whose impact direction is this close to the best match may be picked too, for variety
+pub const FRACTURE_VARIANT_MATCH_TOLERANCE: f32 = 0.15;
+/// Impacts slower than this pick the variants with the fewest shards
+pub const FRACTURE_SLOW_IMPACT_SPEED: f32 = 2.;
+/// Impacts faster than this pick the variants with the most shards
+pub const FRACTURE_FAST_IMPACT_SPEED: f32 = 12.;
+
+/// A precomputed way to break a mesh
+#[derive(Debug, Clone)]
+pub struct FractureVariant {
+    /// Direction of the impact this variant was made for, in the mesh local space
+    pub impact_direction: Vec3,
+    pub shards: Vec<PrefracturedShard>,
+}
+
+/// Impact direction and shards of a fracture variant, before its meshes are added as assets
+pub type FractureVariantShards = (Vec3, Vec<(Mesh, Collider)>);
+/// `None` when the pattern did not break the mesh
+pub type FractureVariantTask = Task<Option<FractureVariantShards>>;
+
+/// Fracture variants of the common meshes, computed while loading
+///
+/// The patterns are rotated around the local Y axis only, which suits the breakable meshes of the arena since they are roughly symmetric around it.
+#[derive(Resource, Default)]
+pub struct FractureLibraries {
+    pub libraries: HashMap<AssetId<Mesh>, Vec<FractureVariant>>,
+}
+impl FractureLibraries {
+    /// Fractures `mesh` with every pattern and orientation, each on a background thread
+    pub fn spawn_variant_tasks(mesh: &Mesh) -> Vec<FractureVariantTask> {
+        let task_pool = AsyncComputeTaskPool::get();
+        let mut tasks = Vec::new();
+        for pattern in FRACTURE_PATTERNS {
+            for i in 0..FRACTURE_PATTERN_ORIENTATIONS {
+                let angle = i as f32 * TAU / FRACTURE_PATTERN_ORIENTATIONS as f32;
+                let rotation = Quat::from_rotation_y(angle);
+                let mesh = mesh.clone();
+                tasks.push(task_pool.spawn(async move {
+                    let shards = fracture_mesh_with_pattern(&mesh, &pattern, rotation);
+                    (shards.len() > 1).then(|| (rotation * Vec3::NEG_Z, shards))
+                }));
+            }
+        }
+        tasks
+    }
+
+    /// Stores the variants computed by [`FractureLibraries::spawn_variant_tasks`] for a mesh
+    pub fn insert_library(
+        &mut self,
+        mesh_id: AssetId<Mesh>,
+        variants_shards: Vec<FractureVariantShards>,
+        meshes_assets: &mut Assets<Mesh>,
+    ) {
+        let variants = variants_shards
+            .into_iter()
+            .map(|(impact_direction, shards)| FractureVariant {
+                impact_direction,
+                shards: shards
+                    .into_iter()
+                    .map(|(shard_mesh, collider)| PrefracturedShard {
+                        center: mesh_center(&shard_mesh),
+                        volume: mesh_volume(&shard_mesh),
+                        mesh: meshes_assets.add(shard_mesh),
+                        collider,
+                    })
+                    .collect(),
+            })
+            .collect();
+        self.libraries.insert(mesh_id, variants);
+    }
+
+    /// Picks a variant made for an impact close to `local_impact_velocity`, or any variant if it is zero
+    ///
+    /// Faster impacts pick the variants with more shards.
+    pub fn pick_variant(
+        &self,
+        mesh_id: AssetId<Mesh>,
+        local_impact_velocity: Vec3,
+    ) -> Option<&FractureVariant> {
+        let variants = self.libraries.get(&mesh_id)?;
+        let mut rng = rand::thread_rng();
+        let Some(direction) = local_impact_velocity.try_normalize() else {
+            return variants.choose(&mut rng);
+        };
+        let best_match = variants
+            .iter()
+            .map(|variant| variant.impact_direction.dot(direction))
+            .reduce(f32::max)?;
+        let candidates: Vec<_> = variants
+            .iter()
+            .filter(|variant| {
+                variant.impact_direction.dot(direction)
+                    >= best_match - FRACTURE_VARIANT_MATCH_TOLERANCE
+            })
+            .collect();
+
+        let min_shards = candidates
+            .iter()
+            .map(|variant| variant.shards.len())
+            .min()?;
+        let max_shards = candidates
+            .iter()
+            .map(|variant| variant.shards.len())
+            .max()?;
+        let t = ((local_impact_velocity.length() - FRACTURE_SLOW_IMPACT_SPEED)
+            / (FRACTURE_FAST_IMPACT_SPEED - FRACTURE_SLOW_IMPACT_SPEED))
+            .clamp(0., 1.);
+        let shard_budget = min_shards as f32 + (max_shards - min_shards) as f32 * t;
+        let shards_mismatch =
+            |variant: &FractureVariant| (variant.shards.len() as f32 - shard_budget).abs();
+        let best_mismatch = candidates
+            .iter()
+            .map(|variant| shards_mismatch(variant))
+            .reduce(f32::min)?;
+        let candidates: Vec<_> = candidates
+            .into_iter()
+            .filter(|variant| shards_mismatch(variant) <= best_mismatch + 0.5)
+            .collect();
+        candidates.choose(&mut rng).copied()
+    }
+}
+
+/// Entity waiting for its shards to be computed. It stays in the world as a stand-in meanwhile.
+#[derive(Component)]
+struct PendingShatter {
+    task: Task<Vec<(Mesh, Collider)>>,
+    /// Kept in the entity local space, since it keeps moving until its shards are ready
+    local_impact_point: Vec3,
+    impact_velocity: Vec3,
+    style: ShardsStyle,
+}
+
+/// Look and lifetime of the shards of an entity
+struct ShardsStyle {
+    material: Handle<StandardMaterial>,
+    lifetime: Duration,
+}
+
+/// A shard about to be spawned
+struct ShardSpawn {
+    mesh: Handle<Mesh>,
+    collider: Collider,
+    /// Whether the mesh was generated for this shard only
+    owns_mesh: bool,
+    /// In the local space of the shattered entity
+    center: Vec3,
+    volume: f32,
+}
+impl ShardSpawn {
+    fn generated(mesh: Mesh, collider: Collider, meshes_assets: &mut Assets<Mesh>) -> Self {
+        Self {
+            center: mesh_center(&mesh),
+            volume: mesh_volume(&mesh),
+            mesh: meshes_assets.add(mesh),
+            collider,
+            owns_mesh: true,
+        }
+    }
+}
+
+/// Splits a mesh along the cuts of `pattern`, rotated by `rotation`, with the colliders of the shards
+pub fn fracture_mesh_with_pattern(
+    mesh: &Mesh,
+    pattern: &FracturePattern,
+    rotation: Quat,
+) -> Vec<(Mesh, Collider)> {
+    let Some(aabb) = mesh.compute_aabb() else {
+        return Vec::new();
+    };
+    let center = Vec3::from(aabb.center);
+    let half_extents = Vec3::from(aabb.half_extents);
+    let mut pieces = vec![mesh.clone()];
+    for cut in pattern.cuts.iter() {
+        let point = center + (rotation * cut.offset) * half_extents;
+        let normal = (rotation * cut.normal).normalize();
+        pieces = pieces
+            .into_iter()
+            .flat_map(|piece| cut_mesh(piece, point, normal))
+            .collect();
+    }
+    with_colliders(pieces)
 }
 
-#[derive(Component, Debug, Clone, PartialEq, Eq, Default, Reflect)]
-struct Shard {
-    despawn_timer: Timer,
+/// Splits a mesh without a fracture library into random shards with `cuts` iterations, with their colliders. The shards close to `local_impact_point` are smaller. Expensive.
+pub fn fracture_mesh(mesh: &Mesh, cuts: u32, local_impact_point: Vec3) -> Vec<(Mesh, Collider)> {
+    let impact_radius = impact_fracture_radius(mesh);
+    let mut rng = rand::thread_rng();
+    let pieces: Vec<Mesh> = slice_bevy_mesh_iterative(mesh, cuts, None)
+        .into_iter()
+        .flat_map(|piece| {
+            let center = mesh_center(&piece);
+            if center.distance(local_impact_point) > impact_radius {
+                return vec![piece];
+            }
+            cut_mesh(piece, center, random_direction(&mut rng))
+        })
+        .collect();
+    with_colliders(pieces)
+}
+
+/// Shards of `mesh` closer to the impact than this are broken once more
+fn impact_fracture_radius(mesh: &Mesh) -> f32 {
+    mesh.compute_aabb()
+        .map(|aabb| Vec3::from(aabb.half_extents).length() * IMPACT_FRACTURE_RADIUS_FACTOR)
+        .unwrap_or(0.)
+}
+
+fn random_direction(rng: &mut impl Rng) -> Vec3 {
+    Vec3::new(
+        rng.gen_range(-1.0..1.0),
+        rng.gen_range(-1.0..1.0),
+        rng.gen_range(-1.0..1.0),
+    )
+    .try_normalize()
+    .unwrap_or(Vec3::Y)
+}
+
+/// Cuts a mesh in two along a plane, or returns it whole if the plane misses it
+fn cut_mesh(mesh: Mesh, point: Vec3, normal: Vec3) -> Vec<Mesh> {
+    let plane = Plane::new(Vec3A::from(point), Vec3A::from(normal).into());
+    match slice_bevy_mesh(plane, &mesh) {
+        Some(halves) => halves.into_iter().collect(),
+        None => vec![mesh],
+    }
+}
+
+fn with_colliders(meshes: Vec<Mesh>) -> Vec<(Mesh, Collider)> {
+    meshes
+        .into_iter()
+        .filter_map(|mesh| {
+            let collider = Collider::from_bevy_mesh(&mesh, &ComputedColliderShape::ConvexHull)?;
+            Some((mesh, collider))
+        })
+        .collect()
 }
 
-// TODO Spawn a parent entity for shattered pieces : add the timer to the parent only
 fn shatter_entity(
     trigger: Trigger<ShatterEntity>,
     mut commands: Commands,
-    mut _materials: ResMut<Assets<StandardMaterial>>,
+    mut debris_budget: ResMut<DebrisBudget>,
+    settings: Res<ShatterSettings>,
+    fracture_libraries: Res<FractureLibraries>,
     mut meshes_assets: ResMut<Assets<Mesh>>,
-    shattered_entity_query: Query<(&Transform, &Handle<StandardMaterial>, &Handle<Mesh>)>,
+    shattered_entity_query: Query<
+        (
+            &Transform,
+            &Handle<StandardMaterial>,
+            &Handle<Mesh>,
+            Option<&Breakable>,
+        ),
+        Without<PendingShatter>,
+    >,
 ) {
     let shatter_info = trigger.event();
 
-    // Despawn the entity
-    commands.entity(shatter_info.entity).despawn_recursive();
-
-    let Ok((transform, mat_handle, mesh_handle)) = shattered_entity_query.get(shatter_info.entity)
+    let Ok((transform, mat_handle, mesh_handle, breakable)) =
+        shattered_entity_query.get(shatter_info.entity)
     else {
         return;
     };
+    let breakable = breakable.cloned().unwrap_or_default();
+    let style = ShardsStyle {
+        material: breakable
+            .material_override
+            .clone()
+            .unwrap_or(mat_handle.clone()),
+        lifetime: Duration::from_millis(breakable.debris_lifetime_ms),
+    };
+    let local_impact_point = transform
+        .compute_affine()
+        .inverse()
+        .transform_point3(shatter_info.impact_point);
+    let local_impact_velocity = transform.rotation.inverse() * shatter_info.impact_velocity;
+
+    // Common meshes were fractured while loading
+    let variant = match (breakable.mode, breakable.shard_count) {
+        (FractureMode::Shatter, None) => {
+            fracture_libraries.pick_variant(mesh_handle.id(), local_impact_velocity)
+        }
+        _ => None,
+    };
+    if let Some(variant) = variant {
+        commands.entity(shatter_info.entity).despawn_recursive();
+        let impact_radius = meshes_assets
+            .get(mesh_handle)
+            .map_or(0., impact_fracture_radius);
+        let mut rng = rand::thread_rng();
+        let mut shards = Vec::with_capacity(variant.shards.len());
+        for shard in variant.shards.iter() {
+            // The prefractured shards close to the impact are broken once more, a single cut each is cheap enough
+            let shard_mesh = if shard.center.distance(local_impact_point) <= impact_radius {
+                meshes_assets.get(&shard.mesh).cloned()
+            } else {
+                None
+            };
+            match shard_mesh {
+                Some(shard_mesh) => shards.extend(
+                    with_colliders(cut_mesh(
+                        shard_mesh,
+                        shard.center,
+                        random_direction(&mut rng),
+                    ))
+                    .into_iter()
+                    .map(|(mesh, collider)| {
+                        ShardSpawn::generated(mesh, collider, &mut meshes_assets)
+                    }),
+                ),
+                None => shards.push(ShardSpawn {
+                    mesh: shard.mesh.clone(),
+                    collider: shard.collider.clone(),
+                    owns_mesh: false,
+                    center: shard.center,
+                    volume: shard.volume,
+                }),
+            }
+        }
+        spawn_shards(
+            &mut commands,
+            &mut debris_budget,
+            transform,
+            &style,
+            local_impact_point,
+            shatter_info.impact_velocity,
+            shards.into_iter(),
+        );
+        return;
+    }
+
     let Some(mesh_to_shatter) = meshes_assets.get(mesh_handle) else {
+        commands.entity(shatter_info.entity).despawn_recursive();
         return;
     };
 
-    // // TODO Link to parent entity with a despawn timer
-    // let shards_parent = commands
-    //     .spawn(Shard {
-    //         despawn_timer: Timer::new(
-    //             Duration::from_millis(SHARDS_DESPAWN_DELAY_MS),
-    //             TimerMode::Once,
-    //         ),
-    //     })
-    //     .id();
-
-    let shards = slice_bevy_mesh_iterative(mesh_to_shatter, SHATTER_ITERATION_COUNT, None);
-    for shard_mesh in shards {
-        let Some(collider) =
-            Collider::from_bevy_mesh(&shard_mesh, &ComputedColliderShape::ConvexHull)
-        else {
+    let shards = match breakable.mode {
+        // A single cut is cheap enough to be done right away
+        FractureMode::Slice => {
+            let normal = local_impact_velocity
+                .cross(Vec3::Y)
+                .try_normalize()
+                .unwrap_or(Vec3::X);
+            with_colliders(cut_mesh(
+                mesh_to_shatter.clone(),
+                mesh_center(mesh_to_shatter),
+                normal,
+            ))
+        }
+        FractureMode::Shatter => {
+            let cuts = breakable.shard_count.unwrap_or(SHATTER_ITERATION_COUNT);
+            if settings.asynchronous {
+                let mesh_to_shatter = mesh_to_shatter.clone();
+                let task = AsyncComputeTaskPool::get().spawn(async move {
+                    fracture_mesh(&mesh_to_shatter, cuts, local_impact_point)
+                });
+                // The entity keeps its physics but loses its gameplay role until it is replaced by its shards
+                commands
+                    .entity(shatter_info.entity)
+                    .remove::<(Jug, Sliceable, SlicedFragment, Breakable)>()
+                    .insert(PendingShatter {
+                        task,
+                        local_impact_point,
+                        impact_velocity: shatter_info.impact_velocity,
+                        style,
+                    });
+                return;
+            }
+            fracture_mesh(mesh_to_shatter, cuts, local_impact_point)
+        }
+    };
+
+    commands.entity(shatter_info.entity).despawn_recursive();
+    let shards: Vec<_> = shards
+        .into_iter()
+        .map(|(mesh, collider)| ShardSpawn::generated(mesh, collider, &mut meshes_assets))
+        .collect();
+    spawn_shards(
+        &mut commands,
+        &mut debris_budget,
+        transform,
+        &style,
+        local_impact_point,
+        shatter_info.impact_velocity,
+        shards.into_iter(),
+    );
+}
+
+fn apply_shatter_results(
+    mut commands: Commands,
+    mut debris_budget: ResMut<DebrisBudget>,
+    mut meshes_assets: ResMut<Assets<Mesh>>,
+    mut pending_query: Query<(Entity, &mut PendingShatter, &Transform)>,
+) {
+    for (entity, mut pending, transform) in pending_query.iter_mut() {
+        let Some(shards) = block_on(future::poll_once(&mut pending.task)) else {
             continue;
         };
-        let mesh_handle = meshes_assets.add(shard_mesh.clone());
+        commands.entity(entity).despawn_recursive();
+        let shards: Vec<_> = shards
+            .into_iter()
+            .map(|(mesh, collider)| ShardSpawn::generated(mesh, collider, &mut meshes_assets))
+            .collect();
+        spawn_shards(
+            &mut commands,
+            &mut debris_budget,
+            transform,
+            &pending.style,
+            pending.local_impact_point,
+            pending.impact_velocity,
+            shards.into_iter(),
+        );
+    }
+}
 
-        let mut rng = rand::thread_rng();
-        let shard_mass = rng.gen_range(FIXED_MIN_SHARD_MASS..FIXED_MAX_SHARD_MASS);
+/// Spawns the shards in a new debris group, pushed away from the impact point
+fn spawn_shards(
+    commands: &mut Commands,
+    debris_budget: &mut DebrisBudget,
+    transform: &Transform,
+    style: &ShardsStyle,
+    local_impact_point: Vec3,
+    impact_velocity: Vec3,
+    shards: impl Iterator<Item = ShardSpawn>,
+) {
+    let shards_parent = spawn_debris_group(
+        commands,
+        debris_budget,
+        "Shards",
+        style.lifetime,
+        DebrisExpiry::Sink,
+    );
+
+    let impact_point = transform.transform_point(local_impact_point);
+    let volume_scale = transform.scale.x * transform.scale.y * transform.scale.z;
+    let mut rng = rand::thread_rng();
+    for shard in shards {
+        let shard_mass = (shard.volume * volume_scale * SHARD_DENSITY).max(MIN_SHARD_MASS);
+
+        // Shards close to the impact take most of the energy
+        let from_impact = transform.transform_point(shard.center) - impact_point;
+        let falloff = 1. / (1. + from_impact.length() / SHARD_IMPULSE_FALLOFF_DISTANCE);
+        let burst_direction = from_impact
+            .try_normalize()
+            .or(impact_velocity.try_normalize())
+            .unwrap_or(Vec3::Y);
+        let impulse = shard_mass
+            * falloff
+            * (impact_velocity * SHARD_IMPACT_VELOCITY_TRANSFER
+                + burst_direction * SHARD_BURST_SPEED);
+        let torque_impulse = falloff
+            * SHARD_MAX_TORQUE_IMPULSE
+            * Vec3::new(
+                rng.gen_range(-1.0..1.0),
+                rng.gen_range(-1.0..1.0),
+                rng.gen_range(-1.0..1.0),
+            );
 
         let shard_entity = commands
             .spawn((
                 Name::new("Shard"),
-                StateScoped(Screen::Playing),
                 PbrBundle {
-                    mesh: mesh_handle.clone(),
-                    transform: Transform::from(*transform),
-                    material: mat_handle.clone(),
+                    mesh: shard.mesh,
+                    transform: *transform,
+                    material: style.material.clone(),
                     ..default()
                 },
                 // Physics
                 RigidBody::Dynamic,
-                collider,
+                shard.collider,
                 ActiveCollisionTypes::default(),
                 Friction::coefficient(0.7),
                 Restitution::coefficient(0.05),
                 ColliderMassProperties::Mass(shard_mass),
+                ExternalImpulse {
+                    impulse,
+                    torque_impulse,
+                },
                 // Logic
-                Shard {
-                    despawn_timer: Timer::new(
-                        Duration::from_millis(SHARDS_DESPAWN_DELAY_MS),
-                        TimerMode::Once,
-                    ),
+                DebrisPiece {
+                    owns_mesh: shard.owns_mesh,
                 },
             ))
             .id();
 
-        // commands.entity(shards_parent).add_child(shard_entity);
+        commands.entity(shards_parent).add_child(shard_entity);
+    }
+}
 
-        // TODO May add impulse to shards
-        // let frag_center: Vec3 = aabb.center.into();
-        // let separating_impulse =
-        //     FRAGMENTS_SEPARATION_IMPULSE_FACTOR * (frag_center - shatter_info.slice_positions.0);
+/// Center of the bounds of a mesh, in local units
+pub fn mesh_center(mesh: &Mesh) -> Vec3 {
+    mesh.compute_aabb()
+        .map(|aabb| Vec3::from(aabb.center))
+        .unwrap_or(Vec3::ZERO)
+}
 
-        commands.entity(shard_entity).insert(ExternalImpulse {
-            impulse: shatter_info.impulse,
-            torque_impulse: Vec3::ZERO,
-        });
-    }
+/// Volume enclosed by a closed triangle mesh, in local units
+pub fn mesh_volume(mesh: &Mesh) -> f32 {
+    let Some(positions) = mesh
+        .attribute(Mesh::ATTRIBUTE_POSITION)
+        .and_then(|positions| positions.as_float3())
+    else {
+        return 0.;
+    };
+    let Some(indices) = mesh.indices() else {
+        return 0.;
+    };
+    let indices: Vec<usize> = indices.iter().collect();
+    // Sum of the signed volumes of the tetrahedrons formed by each triangle and the origin
+    let signed_volume: f32 = indices
+        .chunks_exact(3)
+        .map(|triangle| {
+            let a = Vec3::from(positions[triangle[0]]);
+            let b = Vec3::from(positions[triangle[1]]);
+            let c = Vec3::from(positions[triangle[2]]);
+            a.dot(b.cross(c)) / 6.
+        })
+        .sum();
+    signed_volume.abs()
 }
 
-fn despawn_shards(
-    mut commands: Commands,
-    time: Res<Time>,
-    mut fragments_query: Query<(Entity, &mut Shard)>,
-) {
-    for (entity, mut shards) in fragments_query.iter_mut() {
-        shards.despawn_timer.tick(time.delta());
-        if shards.despawn_timer.finished() {
-            commands.entity(entity).despawn_recursive();
+/// Headless timings of ten simultaneous shatters, run with
+/// `cargo test --release shatter_benchmark -- --ignored --nocapture`
+#[cfg(test)]
+mod benchmark {
+    use std::time::{Duration, Instant};
+
+    use bevy::{
+        math::{primitives::Sphere, Vec3},
+        prelude::{Mesh, Meshable},
+        tasks::{block_on, AsyncComputeTaskPool, TaskPool},
+    };
+
+    use super::{fracture_mesh, SHATTER_ITERATION_COUNT};
+
+    const SHATTER_COUNT: usize = 10;
+    const RUNS: u32 = 5;
+
+    fn shattered_mesh() -> Mesh {
+        Sphere::new(0.5).mesh().ico(4).unwrap()
+    }
+
+    #[test]
+    #[ignore = "benchmark"]
+    fn shatter_benchmark() {
+        let mesh = shattered_mesh();
+        let impact_point = Vec3::new(0., 0., 0.5);
+        AsyncComputeTaskPool::get_or_init(TaskPool::default);
+
+        let mut sync_blocking = Duration::ZERO;
+        let mut async_blocking = Duration::ZERO;
+        let mut async_total = Duration::ZERO;
+        for _ in 0..RUNS {
+            // The frame is blocked until every shatter is done
+            let start = Instant::now();
+            for _ in 0..SHATTER_COUNT {
+                assert!(!fracture_mesh(&mesh, SHATTER_ITERATION_COUNT, impact_point).is_empty());
+            }
+            sync_blocking += start.elapsed();
+
+            // The frame is only blocked while the tasks are spawned
+            let start = Instant::now();
+            let tasks: Vec<_> = (0..SHATTER_COUNT)
+                .map(|_| {
+                    let mesh = mesh.clone();
+                    AsyncComputeTaskPool::get().spawn(async move {
+                        fracture_mesh(&mesh, SHATTER_ITERATION_COUNT, impact_point)
+                    })
+                })
+                .collect();
+            async_blocking += start.elapsed();
+            for task in tasks {
+                assert!(!block_on(task).is_empty());
+            }
+            async_total += start.elapsed();
         }
+
+        println!(
+            "{SHATTER_COUNT} shatters, average over {RUNS} runs: sync blocks {:.2} ms, async blocks {:.2} ms and completes in {:.2} ms",
+            sync_blocking.as_secs_f32() * 1000. / RUNS as f32,
+            async_blocking.as_secs_f32() * 1000. / RUNS as f32,
+            async_total.as_secs_f32() * 1000. / RUNS as f32,
+        );
     }
 }
@@ -0,0 +1,90 @@
+use bevy::{
+    app::{App, Update},
+    math::Vec3,
+    prelude::{
+        in_state, Commands, Component, Entity, Event, IntoSystemConfigs, Query, Res, Transform,
+        With, Without,
+    },
+    reflect::Reflect,
+};
+use bevy_rapier3d::prelude::{RapierConfiguration, Velocity};
+
+use crate::{
+    game::{
+        arena::ArenaMode,
+        ballistics::closest_approach,
+        shield::throwers::THROW_TARGET_TORSO_HEIGHT,
+        spawn::{jug::Jug, player::Player},
+    },
+    AppSet,
+};
+
+/// A jug reaching the gladiator sooner than this is an imminent hit
+pub const IMMINENT_HIT_TIME_MS: u64 = 350;
+/// Jugs passing further than this from the gladiator's torso are not threats
+pub const THREAT_MAX_MISS_DISTANCE: f32 = 2.5;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<ImminentHit>();
+
+    app.add_systems(
+        Update,
+        detect_imminent_hits
+            .in_set(AppSet::Update)
+            .run_if(in_state(ArenaMode::Shield)),
+    );
+}
+
+/// Triggered once per jug about to hit the gladiator
+#[derive(Event, Debug, Clone, Copy, Reflect)]
+pub struct ImminentHit {
+    pub jug: Entity,
+    /// In seconds
+    pub time_to_impact: f32,
+}
+
+/// Added on the jugs that already triggered an [`ImminentHit`]
+#[derive(Component, Debug)]
+pub struct ImminentHitSignaled;
+
+/// Seconds before a jug reaches the gladiator's torso, `None` if it will miss
+pub fn jug_time_to_impact(
+    jug_pos: Vec3,
+    jug_velocity: &Velocity,
+    player_transform: &Transform,
+    rapier_config: &RapierConfiguration,
+) -> Option<f32> {
+    let torso = player_transform.translation + THROW_TARGET_TORSO_HEIGHT * Vec3::Y;
+    closest_approach(jug_pos, jug_velocity.linvel, rapier_config.gravity, torso)
+        .filter(|(_, distance)| *distance <= THREAT_MAX_MISS_DISTANCE)
+        .map(|(time, _)| time)
+}
+
+fn detect_imminent_hits(
+    mut commands: Commands,
+    rapier_config: Res<RapierConfiguration>,
+    player_query: Query<&Transform, With<Player>>,
+    jugs_query: Query<(Entity, &Transform, &Velocity), (With<Jug>, Without<ImminentHitSignaled>)>,
+) {
+    let Ok(player_transform) = player_query.get_single() else {
+        return;
+    };
+    let imminent_time = IMMINENT_HIT_TIME_MS as f32 / 1000.;
+    for (jug, jug_transform, jug_velocity) in jugs_query.iter() {
+        let Some(time_to_impact) = jug_time_to_impact(
+            jug_transform.translation,
+            jug_velocity,
+            player_transform,
+            &rapier_config,
+        ) else {
+            continue;
+        };
+        if time_to_impact <= imminent_time {
+            commands.entity(jug).insert(ImminentHitSignaled);
+            commands.trigger(ImminentHit {
+                jug,
+                time_to_impact,
+            });
+        }
+    }
+}
@@ -4,16 +4,24 @@ use bevy::{
         in_state, Commands, EventReader, IntoSystemConfigs, Query, Transform, With, Without,
     },
 };
-use bevy_rapier3d::prelude::CollisionEvent;
+use bevy_rapier3d::prelude::{CollisionEvent, Velocity};
 
 use crate::game::{
     arena::ArenaMode,
+    breakable::DamageBreakable,
     score::{ScoreAction, ScoreActionType},
-    shattering::ShatterEntity,
-    spawn::{player::Player, shield::Shield},
+    spawn::{jug::Jug, player::Player, shield::Shield},
 };
 
-pub const SHATTER_IMPULSE_FACTOR: f32 = 2.;
+/// Distance from the center of a jug to the point where it is hit
+pub const JUG_IMPACT_RADIUS: f32 = 0.3;
+/// How much of the jug speed is sent back into its shards when it hits the shield
+pub const SHIELD_IMPACT_VELOCITY_FACTOR: f32 = 1.;
+/// The gladiator's body absorbs most of the impact
+pub const PLAYER_IMPACT_VELOCITY_FACTOR: f32 = 0.3;
+/// Damage dealt to the jugs, enough to break them
+pub const SHIELD_HIT_DAMAGE: f32 = 1.;
+pub const PLAYER_HIT_DAMAGE: f32 = 1.;
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(
@@ -27,7 +35,7 @@ fn handle_shield_collisions(
     mut collision_events: EventReader<CollisionEvent>,
     // mut contact_force_events: EventReader<ContactForceEvent>,
     shield_query: Query<&Transform, With<Shield>>,
-    other_transforms_query: Query<&Transform, Without<Shield>>,
+    other_transforms_query: Query<(&Transform, &Velocity), (With<Jug>, Without<Shield>)>,
 ) {
     for collision_event in collision_events.read() {
         let CollisionEvent::Started(e1, e2, _flags) = collision_event else {
@@ -41,7 +49,7 @@ fn handle_shield_collisions(
             // TODO Jug versus ground/character ? just bounce ? At least despawn timer (could be on the jug itself already as sonn as it is spawned)
             continue;
         };
-        let Ok(jug_transfrom) = other_transforms_query.get(jug_entity) else {
+        let Ok((jug_transfrom, jug_velocity)) = other_transforms_query.get(jug_entity) else {
             continue;
         };
         // TODO action type
@@ -52,11 +60,13 @@ fn handle_shield_collisions(
 
         let impact_direction =
             (shield_transform.translation - jug_transfrom.translation).normalize();
-        // TODO Depend on speed of the jug
-        let impulse = -SHATTER_IMPULSE_FACTOR * impact_direction;
-        commands.trigger(ShatterEntity {
+        commands.trigger(DamageBreakable {
             entity: jug_entity,
-            impulse,
+            damage: SHIELD_HIT_DAMAGE,
+            impact_point: jug_transfrom.translation + JUG_IMPACT_RADIUS * impact_direction,
+            impact_velocity: -SHIELD_IMPACT_VELOCITY_FACTOR
+                * jug_velocity.linvel.length()
+                * impact_direction,
         });
         // TODO Collision with gladiator: should trigger a MissEvent (and impulse the jug in the other direction ?)
     }
@@ -67,7 +77,7 @@ fn handle_player_collisions(
     mut collision_events: EventReader<CollisionEvent>,
     // mut contact_force_events: EventReader<ContactForceEvent>,
     player_query: Query<&Transform, With<Player>>,
-    other_transforms_query: Query<&Transform, Without<Player>>,
+    other_transforms_query: Query<(&Transform, &Velocity), (With<Jug>, Without<Player>)>,
 ) {
     for collision_event in collision_events.read() {
         let CollisionEvent::Started(e1, e2, _flags) = collision_event else {
@@ -81,7 +91,7 @@ fn handle_player_collisions(
             // TODO Jug versus ground/character ? just bounce ? At least despawn timer (could be on the jug itself already as sonn as it is spawned)
             continue;
         };
-        let Ok(jug_transfrom) = other_transforms_query.get(jug_entity) else {
+        let Ok((jug_transfrom, jug_velocity)) = other_transforms_query.get(jug_entity) else {
             continue;
         };
         commands.trigger(ScoreAction {
@@ -91,11 +101,13 @@ fn handle_player_collisions(
 
         let impact_direction =
             (player_transform.translation - jug_transfrom.translation).normalize();
-        // TODO Depend on speed of the jug
-        let impulse = SHATTER_IMPULSE_FACTOR * impact_direction;
-        commands.trigger(ShatterEntity {
+        commands.trigger(DamageBreakable {
             entity: jug_entity,
-            impulse,
+            damage: PLAYER_HIT_DAMAGE,
+            impact_point: jug_transfrom.translation + JUG_IMPACT_RADIUS * impact_direction,
+            impact_velocity: -PLAYER_IMPACT_VELOCITY_FACTOR
+                * jug_velocity.linvel.length()
+                * impact_direction,
         });
         // TODO Collision with gladiator: should trigger a MissEvent (and impulse the jug in the other direction ?)
     }
@@ -3,13 +3,16 @@ use bevy::{
     prelude::{Commands, OnEnter, OnExit},
 };
 use camera::SetShieldModeCamera;
+use player_control::ShieldTurnController;
 use throwers::{SpawnJugThrowers, ThrowersData};
 
 use super::{arena::ArenaMode, spawn::shield::SpawnShield};
 
+pub mod blocking;
 pub mod camera;
 pub mod collisions;
 pub mod player_control;
+pub mod threats;
 pub mod throwers;
 
 pub(super) fn plugin(app: &mut App) {
@@ -18,6 +21,8 @@ pub(super) fn plugin(app: &mut App) {
         throwers::plugin,
         player_control::plugin,
         collisions::plugin,
+        blocking::plugin,
+        threats::plugin,
     ));
     app.add_systems(OnEnter(ArenaMode::Shield), on_enter_shield_mode);
     app.add_systems(OnExit(ArenaMode::Shield), on_exit_shield_mode);
@@ -25,6 +30,7 @@ pub(super) fn plugin(app: &mut App) {
 
 pub fn on_enter_shield_mode(mut commands: Commands) {
     commands.insert_resource(ThrowersData::default());
+    commands.insert_resource(ShieldTurnController::default());
     commands.trigger(SpawnShield {
         scope: ArenaMode::Shield,
     });
@@ -34,4 +40,5 @@ pub fn on_enter_shield_mode(mut commands: Commands) {
 
 pub fn on_exit_shield_mode(mut commands: Commands) {
     commands.remove_resource::<ThrowersData>();
+    commands.remove_resource::<ShieldTurnController>();
 }
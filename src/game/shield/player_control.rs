@@ -1,44 +1,155 @@
+use std::f32::consts::{PI, TAU};
+
 use bevy::{
     app::{App, Update},
-    math::Vec3,
-    prelude::{in_state, IntoSystemConfigs, Query, Res, Transform, With},
-    utils::default,
+    math::{Quat, Vec3},
+    prelude::{
+        in_state, not, IntoSystemConfigs, Query, ReflectResource, Res, ResMut, Resource, Transform,
+        With, Without,
+    },
+    reflect::Reflect,
+    time::Time,
 };
-use bevy_mod_raycast::{cursor::CursorRay, prelude::Raycast};
+use bevy_mod_raycast::cursor::CursorRay;
 
 use crate::{
-    game::{arena::ArenaMode, spawn::player::Player},
+    game::{
+        arena::ArenaMode,
+        player_camera::player_camera_dragging,
+        powerups::{power_up_active, PowerUpKind},
+        raycasting::FilteredRaycast,
+        spawn::{jug::Jug, player::Player},
+    },
     AppSet,
 };
 
+/// Max turn rate of the gladiator holding the shield, in radians per second
+pub const SHIELD_MAX_TURN_RATE: f32 = 7.;
+/// In radians per second squared, used to speed up and to brake
+pub const SHIELD_TURN_ACCELERATION: f32 = 45.;
+
 pub(super) fn plugin(app: &mut App) {
+    app.register_type::<ShieldTurnController>();
+
     app.add_systems(
         Update,
-        (rotate_player.in_set(AppSet::RecordInput)).run_if(in_state(ArenaMode::Shield)),
+        (
+            // The cursor moves with the camera while it is dragged
+            aim_with_cursor
+                .in_set(AppSet::RecordInput)
+                .run_if(not(player_camera_dragging)),
+            auto_block
+                .in_set(AppSet::Update)
+                .run_if(power_up_active(PowerUpKind::AutoBlock)),
+            turn_player.in_set(AppSet::Update).after(auto_block),
+        )
+            .run_if(in_state(ArenaMode::Shield)),
     );
 }
 
-fn rotate_player(
+/// Turns the gladiator towards the aimed direction, with a limited turn rate and acceleration
+#[derive(Resource, Debug, Default, Reflect)]
+#[reflect(Resource)]
+pub struct ShieldTurnController {
+    /// Yaw aimed at, in radians
+    pub target_yaw: Option<f32>,
+    /// Current turn rate, in radians per second
+    pub yaw_velocity: f32,
+}
+
+/// Yaw that makes the gladiator look towards `direction`
+fn yaw_towards(direction: Vec3) -> f32 {
+    (-direction.x).atan2(-direction.z)
+}
+
+/// Angle from `from` to `to`, in `[-PI, PI)`
+fn shortest_angle(from: f32, to: f32) -> f32 {
+    (to - from + PI).rem_euclid(TAU) - PI
+}
+
+fn aim_with_cursor(
     cursor_ray: Res<CursorRay>,
-    mut players_query: Query<&mut Transform, With<Player>>,
-    mut raycast: Raycast,
+    raycast: FilteredRaycast,
+    players_query: Query<&Transform, With<Player>>,
+    mut controller: ResMut<ShieldTurnController>,
 ) {
-    let Ok(mut player_transform) = players_query.get_single_mut() else {
+    let Ok(player_transform) = players_query.get_single() else {
         return;
     };
     let Some(cursor_ray) = cursor_ray.0 else {
         return;
     };
 
-    // TODO Raycast with mouse
-    // raycast.debug_cast_ray(cursor_ray, &default(), &mut gizmos);
-    let hits = raycast.cast_ray(cursor_ray, &default());
-    let Some(hit) = hits.get(0) else {
+    // Jugs, the gladiator and the arena walls under the cursor are ignored
+    let Some(aim_point) = raycast.aim_point(cursor_ray, player_transform.translation) else {
         return;
     };
 
-    let mut direction = hit.1.position() - player_transform.translation;
-    // Quick & dirty, no rotation around x and z
+    let mut direction = aim_point - player_transform.translation;
+    // No rotation around x and z
+    direction.y = 0.;
+    if direction.length_squared() > f32::EPSILON {
+        controller.target_yaw = Some(yaw_towards(direction));
+    }
+}
+
+/// Overrides the cursor and aims towards the closest jug
+fn auto_block(
+    players_query: Query<&Transform, With<Player>>,
+    jugs_query: Query<&Transform, (With<Jug>, Without<Player>)>,
+    mut controller: ResMut<ShieldTurnController>,
+) {
+    let Ok(player_transform) = players_query.get_single() else {
+        return;
+    };
+    let player_pos = player_transform.translation;
+    let Some(closest_jug) = jugs_query.iter().min_by(|a, b| {
+        a.translation
+            .distance_squared(player_pos)
+            .total_cmp(&b.translation.distance_squared(player_pos))
+    }) else {
+        return;
+    };
+    let mut direction = closest_jug.translation - player_pos;
     direction.y = 0.;
-    player_transform.look_to(direction.normalize(), Vec3::Y);
+    if direction.length_squared() > f32::EPSILON {
+        controller.target_yaw = Some(yaw_towards(direction));
+    }
+}
+
+fn turn_player(
+    time: Res<Time>,
+    mut controller: ResMut<ShieldTurnController>,
+    mut players_query: Query<&mut Transform, With<Player>>,
+) {
+    let Ok(mut player_transform) = players_query.get_single_mut() else {
+        return;
+    };
+    let Some(target_yaw) = controller.target_yaw else {
+        return;
+    };
+    let delta_seconds = time.delta_seconds();
+    if delta_seconds <= 0. {
+        return;
+    }
+
+    let yaw = yaw_towards(*player_transform.forward());
+    let remaining = shortest_angle(yaw, target_yaw);
+    // Fastest turn rate that can still stop on the target
+    let desired_velocity = remaining.signum()
+        * (2. * SHIELD_TURN_ACCELERATION * remaining.abs())
+            .sqrt()
+            .min(SHIELD_MAX_TURN_RATE);
+    let max_velocity_change = SHIELD_TURN_ACCELERATION * delta_seconds;
+    controller.yaw_velocity += (desired_velocity - controller.yaw_velocity)
+        .clamp(-max_velocity_change, max_velocity_change);
+
+    let step = controller.yaw_velocity * delta_seconds;
+    let new_yaw = if step.abs() >= remaining.abs() && step.signum() == remaining.signum() {
+        controller.yaw_velocity = 0.;
+        target_yaw
+    } else {
+        yaw + step
+    };
+    player_transform.rotation = Quat::from_rotation_y(new_yaw);
 }
@@ -0,0 +1,292 @@
+//! HUD arrows along the screen edge pointing at the off-screen jugs about to hit the gladiator.
+
+use std::{collections::HashSet, f32::consts::FRAC_PI_4, time::Duration};
+
+use bevy::{
+    app::{App, Update},
+    asset::{Assets, Handle},
+    audio::{Pitch, PitchBundle, PlaybackSettings, Volume},
+    color::{Color, Srgba},
+    core::Name,
+    math::{Quat, Vec2, Vec3},
+    prelude::{
+        in_state, BuildChildren, Camera, Children, Commands, Component, DespawnRecursiveExt,
+        Entity, FromWorld, GlobalTransform, IntoSystemConfigs, IsDefaultUiCamera, NodeBundle,
+        OnEnter, Query, Res, Resource, StateScoped, Transform, TransformBundle, With, Without,
+        World,
+    },
+    ui::{AlignItems, BackgroundColor, JustifyContent, PositionType, Style, UiRect, Val},
+    utils::default,
+};
+use bevy_rapier3d::prelude::{RapierConfiguration, Velocity};
+
+use crate::{
+    game::{
+        accessibility::AccessibilitySettings,
+        arena::ArenaMode,
+        shield::blocking::{jug_time_to_impact, IMMINENT_HIT_TIME_MS},
+        spawn::{jug::Jug, player::Player},
+    },
+    ui::prelude::*,
+    AppSet,
+};
+
+pub const THREAT_INDICATOR_SIZE: f32 = 36.;
+/// Distance between the indicators and the screen edge, in logical pixels
+pub const THREAT_INDICATOR_EDGE_MARGIN: f32 = 30.;
+/// Threats further than this in time get the distant colour
+pub const THREAT_DISTANT_TIME_MS: u64 = 1500;
+pub const THREAT_IMMINENT_COLOR: Srgba = Srgba::rgb(0.95, 0.1, 0.05);
+pub const THREAT_DISTANT_COLOR: Srgba = Srgba::rgb(1., 0.85, 0.2);
+
+pub const THREAT_PING_FREQUENCY: f32 = 880.;
+pub const THREAT_PING_DURATION_MS: u64 = 80;
+pub const THREAT_PING_VOLUME: f32 = 0.3;
+/// The ping is emitted at this distance from the camera, towards the thrown jug, so that only its direction matters
+pub const THREAT_PING_DISTANCE: f32 = 3.;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<ThreatPingCachedData>();
+
+    app.add_systems(OnEnter(ArenaMode::Shield), spawn_threat_indicators_hud);
+    app.add_systems(
+        Update,
+        update_threat_indicators
+            .in_set(AppSet::Update)
+            .run_if(in_state(ArenaMode::Shield)),
+    );
+}
+
+#[derive(Component, Debug)]
+pub struct ThreatIndicatorsHud;
+
+#[derive(Component, Debug)]
+pub struct ThreatIndicator {
+    pub jug: Entity,
+}
+
+#[derive(Resource)]
+pub struct ThreatPingCachedData {
+    pitch: Handle<Pitch>,
+}
+
+impl FromWorld for ThreatPingCachedData {
+    fn from_world(world: &mut World) -> Self {
+        let mut pitches = world.resource_mut::<Assets<Pitch>>();
+        Self {
+            pitch: pitches.add(Pitch::new(
+                THREAT_PING_FREQUENCY,
+                Duration::from_millis(THREAT_PING_DURATION_MS),
+            )),
+        }
+    }
+}
+
+fn spawn_threat_indicators_hud(mut commands: Commands) {
+    commands
+        .ui_root()
+        .insert((ThreatIndicatorsHud, StateScoped(ArenaMode::Shield)));
+}
+
+/// Colour of an indicator for a jug hitting in `time_to_impact` seconds
+fn threat_color(time_to_impact: f32) -> Color {
+    let imminent_time = IMMINENT_HIT_TIME_MS as f32 / 1000.;
+    let distant_time = THREAT_DISTANT_TIME_MS as f32 / 1000.;
+    let t = ((time_to_impact - imminent_time) / (distant_time - imminent_time)).clamp(0., 1.);
+    let (from, to) = (THREAT_IMMINENT_COLOR, THREAT_DISTANT_COLOR);
+    Color::srgb(
+        from.red + (to.red - from.red) * t,
+        from.green + (to.green - from.green) * t,
+        from.blue + (to.blue - from.blue) * t,
+    )
+}
+
+/// Position on the screen edge and angle of the indicator for `target`, `None` when it is visible on screen
+fn indicator_placement(
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+    target: Vec3,
+) -> Option<(Vec2, f32)> {
+    let viewport_size = camera.logical_viewport_size()?;
+    if let Some(pos) = camera.world_to_viewport(camera_transform, target) {
+        if pos.cmpge(Vec2::ZERO).all() && pos.cmple(viewport_size).all() {
+            return None;
+        }
+    }
+    // In camera space, which also works for targets behind the camera. Screen y goes down.
+    let local = camera_transform.affine().inverse().transform_point3(target);
+    let direction = Vec2::new(local.x, -local.y)
+        .try_normalize()
+        .unwrap_or(Vec2::Y);
+
+    let half_size = viewport_size / 2.;
+    let inner_half_size = (half_size - Vec2::splat(THREAT_INDICATOR_EDGE_MARGIN)).max(Vec2::ZERO);
+    let edge_distance =
+        (inner_half_size.x / direction.x.abs()).min(inner_half_size.y / direction.y.abs());
+    Some((
+        half_size + direction * edge_distance,
+        direction.y.atan2(direction.x),
+    ))
+}
+
+fn spawn_threat_indicator(
+    commands: &mut Commands,
+    hud: Entity,
+    jug: Entity,
+    pos: Vec2,
+    angle: f32,
+    color: Color,
+) {
+    commands.entity(hud).with_children(|children| {
+        children
+            .spawn((
+                Name::new("Threat indicator"),
+                ThreatIndicator { jug },
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        left: Val::Px(pos.x - THREAT_INDICATOR_SIZE / 2.),
+                        top: Val::Px(pos.y - THREAT_INDICATOR_SIZE / 2.),
+                        width: Val::Px(THREAT_INDICATOR_SIZE),
+                        height: Val::Px(THREAT_INDICATOR_SIZE),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    transform: Transform::from_rotation(Quat::from_rotation_z(angle)),
+                    ..default()
+                },
+            ))
+            .with_children(|arrow| {
+                // Pointing to the right before rotation: a shaft and a diamond head
+                arrow.spawn(NodeBundle {
+                    style: Style {
+                        width: Val::Px(THREAT_INDICATOR_SIZE * 0.5),
+                        height: Val::Px(THREAT_INDICATOR_SIZE * 0.2),
+                        ..default()
+                    },
+                    background_color: color.into(),
+                    ..default()
+                });
+                arrow.spawn(NodeBundle {
+                    style: Style {
+                        width: Val::Px(THREAT_INDICATOR_SIZE * 0.4),
+                        height: Val::Px(THREAT_INDICATOR_SIZE * 0.4),
+                        margin: UiRect::left(Val::Px(-THREAT_INDICATOR_SIZE * 0.15)),
+                        ..default()
+                    },
+                    transform: Transform::from_rotation(Quat::from_rotation_z(FRAC_PI_4)),
+                    background_color: color.into(),
+                    ..default()
+                });
+            });
+    });
+}
+
+fn update_threat_indicators(
+    mut commands: Commands,
+    accessibility: Res<AccessibilitySettings>,
+    rapier_config: Res<RapierConfiguration>,
+    ping_data: Res<ThreatPingCachedData>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<IsDefaultUiCamera>>,
+    player_query: Query<&Transform, With<Player>>,
+    jugs_query: Query<(Entity, &Transform, &Velocity), With<Jug>>,
+    hud_query: Query<Entity, With<ThreatIndicatorsHud>>,
+    mut indicators_query: Query<
+        (
+            Entity,
+            &ThreatIndicator,
+            &mut Style,
+            &mut Transform,
+            &Children,
+        ),
+        (Without<Player>, Without<Jug>),
+    >,
+    mut colors_query: Query<&mut BackgroundColor>,
+) {
+    let (Ok((camera, camera_transform)), Ok(player_transform), Ok(hud)) = (
+        camera_query.get_single(),
+        player_query.get_single(),
+        hud_query.get_single(),
+    ) else {
+        return;
+    };
+
+    let mut indicated_jugs = HashSet::new();
+    for (indicator, threat, mut style, mut transform, children) in indicators_query.iter_mut() {
+        let placement =
+            jugs_query
+                .get(threat.jug)
+                .ok()
+                .and_then(|(_, jug_transform, jug_velocity)| {
+                    let time_to_impact = jug_time_to_impact(
+                        jug_transform.translation,
+                        jug_velocity,
+                        player_transform,
+                        &rapier_config,
+                    )?;
+                    let (pos, angle) =
+                        indicator_placement(camera, camera_transform, jug_transform.translation)?;
+                    Some((pos, angle, time_to_impact))
+                });
+        let Some((pos, angle, time_to_impact)) = placement else {
+            commands.entity(indicator).despawn_recursive();
+            continue;
+        };
+        indicated_jugs.insert(threat.jug);
+
+        style.left = Val::Px(pos.x - THREAT_INDICATOR_SIZE / 2.);
+        style.top = Val::Px(pos.y - THREAT_INDICATOR_SIZE / 2.);
+        transform.rotation = Quat::from_rotation_z(angle);
+        let color = threat_color(time_to_impact);
+        let mut parts = colors_query.iter_many_mut(children.iter());
+        while let Some(mut background) = parts.fetch_next() {
+            background.0 = color;
+        }
+    }
+
+    for (jug, jug_transform, jug_velocity) in jugs_query.iter() {
+        if indicated_jugs.contains(&jug) {
+            continue;
+        }
+        let Some(time_to_impact) = jug_time_to_impact(
+            jug_transform.translation,
+            jug_velocity,
+            player_transform,
+            &rapier_config,
+        ) else {
+            continue;
+        };
+        let Some((pos, angle)) =
+            indicator_placement(camera, camera_transform, jug_transform.translation)
+        else {
+            continue;
+        };
+        spawn_threat_indicator(
+            &mut commands,
+            hud,
+            jug,
+            pos,
+            angle,
+            threat_color(time_to_impact),
+        );
+
+        if accessibility.threat_ping {
+            let camera_pos = camera_transform.translation();
+            let ping_direction = (jug_transform.translation - camera_pos).normalize_or_zero();
+            commands.spawn((
+                Name::new("Threat ping"),
+                PitchBundle {
+                    source: ping_data.pitch.clone_weak(),
+                    settings: PlaybackSettings::DESPAWN
+                        .with_spatial(true)
+                        .with_volume(Volume::new(THREAT_PING_VOLUME)),
+                },
+                TransformBundle::from_transform(Transform::from_translation(
+                    camera_pos + THREAT_PING_DISTANCE * ping_direction,
+                )),
+                StateScoped(ArenaMode::Shield),
+            ));
+        }
+    }
+}
@@ -11,7 +11,7 @@ use bevy::{
     reflect::Reflect,
     time::{Time, Timer, TimerMode},
 };
-use rand::Rng;
+use rand::{distributions::Standard, prelude::Distribution, Rng};
 
 use crate::game::{
     arena::ArenaMode,
@@ -35,9 +35,19 @@ pub const JUG_THROWERS_POSITIONS: [Vec3; 8] = [
     Vec3::new(0., 0., -JUG_SPAWN_RADIUS),
 ];
 
+pub const INITIAL_JUG_FLIGHT_TIME_MS: u64 = 1500;
+pub const FINAL_JUG_FLIGHT_TIME_DELTA_MS: u64 = 550;
+/// Probability for a thrower to aim where the target is going rather than where it is
+pub const LEAD_TARGET_PROBABILITY: f64 = 0.5;
+
+pub const THROW_TARGET_HEAD_HEIGHT: f32 = 4.;
+pub const THROW_TARGET_TORSO_HEIGHT: f32 = 3.;
+pub const THROW_TARGET_LEGS_HEIGHT: f32 = 1.5;
+
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<ThrowersData>();
     app.register_type::<Thrower>();
+    app.register_type::<ThrowTarget>();
 
     app.add_systems(Update, (throw_jugs).run_if(in_state(ArenaMode::Shield)));
 
@@ -47,10 +57,15 @@ pub(super) fn plugin(app: &mut App) {
 #[derive(Resource, Reflect)]
 pub struct ThrowersData {
     throwers: Vec<Entity>,
-    // TODO If throwing takes time, needs to pick a free thrower
+    /// Throwers currently winding up or throwing
+    busy_throwers: Vec<Entity>,
     next_throw_timer: Timer,
     min_throw_interval_ms: u64,
     max_throw_interval_ms: u64,
+    /// Replaces the throw interval range given by the difficulty
+    pub throw_interval_ms: Option<(u64, u64)>,
+    /// Replaces the flight time given by the difficulty, shorter is faster
+    pub jug_flight_time_ms: Option<u64>,
 }
 pub const INITIAL_MIN_THROW_INTERVAL_MS: u64 = 750;
 pub const INITIAL_MAX_THROW_INTERVAL_MS: u64 = 1800;
@@ -62,16 +77,63 @@ impl Default for ThrowersData {
     fn default() -> Self {
         Self {
             throwers: Default::default(),
+            busy_throwers: Default::default(),
             next_throw_timer: Timer::new(
                 Duration::from_millis(INITIAL_MAX_THROW_INTERVAL_MS),
                 TimerMode::Once,
             ),
             min_throw_interval_ms: INITIAL_MIN_THROW_INTERVAL_MS,
             max_throw_interval_ms: INITIAL_MAX_THROW_INTERVAL_MS,
+            throw_interval_ms: None,
+            jug_flight_time_ms: None,
+        }
+    }
+}
+
+/// Part of the gladiator aimed at by a thrower
+#[derive(Reflect, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ThrowTarget {
+    Head,
+    Torso,
+    Legs,
+}
+impl ThrowTarget {
+    /// Height of the aimed point above the gladiator's feet
+    pub fn height(&self) -> f32 {
+        match self {
+            ThrowTarget::Head => THROW_TARGET_HEAD_HEIGHT,
+            ThrowTarget::Torso => THROW_TARGET_TORSO_HEIGHT,
+            ThrowTarget::Legs => THROW_TARGET_LEGS_HEIGHT,
+        }
+    }
+}
+
+impl Distribution<ThrowTarget> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> ThrowTarget {
+        match rng.gen_range(0..=2) {
+            0 => ThrowTarget::Head,
+            1 => ThrowTarget::Torso,
+            _ => ThrowTarget::Legs,
         }
     }
 }
 
+impl ThrowersData {
+    pub fn is_busy(&self, thrower: Entity) -> bool {
+        self.busy_throwers.contains(&thrower)
+    }
+
+    pub fn set_busy(&mut self, thrower: Entity) {
+        if !self.is_busy(thrower) {
+            self.busy_throwers.push(thrower);
+        }
+    }
+
+    pub fn set_free(&mut self, thrower: Entity) {
+        self.busy_throwers.retain(|busy| *busy != thrower);
+    }
+}
+
 #[derive(Event, Debug)]
 pub struct SpawnJugThrowers;
 
@@ -121,16 +183,32 @@ fn throw_jugs(
 ) {
     jug_throwers.next_throw_timer.tick(time.delta());
     if jug_throwers.next_throw_timer.finished() {
+        let free_throwers: Vec<Entity> = jug_throwers
+            .throwers
+            .iter()
+            .filter(|thrower| !jug_throwers.is_busy(**thrower))
+            .copied()
+            .collect();
+        if free_throwers.is_empty() {
+            return;
+        }
         let mut rng = rand::thread_rng();
-        // TODO Throw animation/build up
-        let index = rng.gen_range(0..jug_throwers.throwers.len());
+        let thrower = free_throwers[rng.gen_range(0..free_throwers.len())];
+        let flight_time_ms = jug_throwers.jug_flight_time_ms.unwrap_or(
+            INITIAL_JUG_FLIGHT_TIME_MS
+                - (difficulty.difficulty_factor_0_1() * FINAL_JUG_FLIGHT_TIME_DELTA_MS as f32)
+                    as u64,
+        );
         commands.trigger_targets(
             ThrowJug {
-                at: DEFAULT_GLADIATOR_POS,
+                aim: rng.gen(),
+                flight_time: Duration::from_millis(flight_time_ms).as_secs_f32(),
+                lead_target: rng.gen_bool(LEAD_TARGET_PROBABILITY),
                 scope: cycle.current_mode,
             },
-            jug_throwers.throwers[index],
+            thrower,
         );
+        jug_throwers.set_busy(thrower);
 
         // Prepare next throw
         jug_throwers.min_throw_interval_ms = INITIAL_MIN_THROW_INTERVAL_MS
@@ -140,6 +218,11 @@ fn throw_jugs(
             - (difficulty.difficulty_factor_0_1() * FINAL_MAX_THROW_INTERVAL_DELTA_MS as f32)
                 as u64;
 
+        if let Some((min_interval_ms, max_interval_ms)) = jug_throwers.throw_interval_ms {
+            jug_throwers.min_throw_interval_ms = min_interval_ms;
+            jug_throwers.max_throw_interval_ms = max_interval_ms;
+        }
+
         jug_throwers.next_throw_timer =
             Timer::new(
                 Duration::from_millis(rng.gen_range(
@@ -1,17 +1,21 @@
 use std::time::Duration;
 
-use bevy::{gltf::GltfMesh, prelude::*};
+use bevy::{gltf::GltfMesh, prelude::*, utils::HashMap};
 use bevy_rapier3d::prelude::{
     ActiveCollisionTypes, Collider, ColliderMassProperties, Friction, Restitution, RigidBody,
 };
 use bevy_tweening::{lens::TransformPositionLens, Animator, EaseFunction, Tween};
+use rand::Rng;
 
 use crate::{
     game::{
         arena::ArenaMode,
         assets::{GltfKey, HandleMap, ASSETS_SCALE},
         score::Difficulty,
-        sword::slicing::Sliceable,
+        sword::{
+            dummies::DummiesModeData,
+            slicing::{SliceArmor, Sliceable},
+        },
     },
     screen::Screen,
 };
@@ -24,17 +28,128 @@ pub const FINAL_DUMMY_DESPAWN_TIMER_MS: u64 = 1700;
 pub const DUMMY_FALL_ANIMATION_DURATION_MS: u64 = 1000;
 pub const DUMMY_FALL_START_UP_DELTA: f32 = 25.;
 
+/// Data defining each kind of dummy
+pub const DUMMY_ARCHETYPES: [DummyArchetype; 5] = [
+    DummyArchetype {
+        kind: DummyKind::Regular,
+        slices_to_kill: 1,
+        lifetime_factor: 1.,
+        is_decoy: false,
+        move_interval_ms: None,
+        tint: Color::WHITE,
+        spawn_weights: (1., 0.45),
+    },
+    DummyArchetype {
+        kind: DummyKind::Armoured,
+        slices_to_kill: 2,
+        lifetime_factor: 1.6,
+        is_decoy: false,
+        move_interval_ms: None,
+        tint: Color::srgb(0.55, 0.6, 0.7),
+        spawn_weights: (0.1, 0.2),
+    },
+    DummyArchetype {
+        kind: DummyKind::Fleeting,
+        slices_to_kill: 1,
+        lifetime_factor: 0.55,
+        is_decoy: false,
+        move_interval_ms: None,
+        tint: Color::srgb(1., 0.85, 0.3),
+        spawn_weights: (0.05, 0.15),
+    },
+    DummyArchetype {
+        kind: DummyKind::Decoy,
+        slices_to_kill: 1,
+        lifetime_factor: 0.9,
+        is_decoy: true,
+        move_interval_ms: None,
+        tint: Color::srgb(0.9, 0.25, 0.2),
+        spawn_weights: (0.05, 0.1),
+    },
+    DummyArchetype {
+        kind: DummyKind::Moving,
+        slices_to_kill: 1,
+        lifetime_factor: 1.4,
+        is_decoy: false,
+        move_interval_ms: Some(700),
+        tint: Color::srgb(0.4, 0.8, 0.45),
+        spawn_weights: (0., 0.1),
+    },
+];
+
 pub(super) fn plugin(app: &mut App) {
     app.observe(spawn_dummy);
     app.register_type::<Dummy>();
+    app.register_type::<DummyKind>();
+    app.register_type::<DummyMover>();
 
     app.add_systems(Update, attach_dummy_logic.run_if(in_state(Screen::Playing)));
 }
 
+#[derive(Reflect, Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub enum DummyKind {
+    #[default]
+    Regular,
+    /// Needs to be sliced more than once
+    Armoured,
+    /// Short window to slice it
+    Fleeting,
+    /// Should not be sliced
+    Decoy,
+    /// Slides between dummy slots
+    Moving,
+}
+impl DummyKind {
+    pub fn archetype(&self) -> &'static DummyArchetype {
+        DUMMY_ARCHETYPES
+            .iter()
+            .find(|archetype| archetype.kind == *self)
+            .unwrap_or(&DUMMY_ARCHETYPES[0])
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DummyArchetype {
+    pub kind: DummyKind,
+    pub slices_to_kill: u32,
+    /// Multiplier applied to the despawn delay
+    pub lifetime_factor: f32,
+    /// Slicing a decoy is a mistake, ignoring it is not
+    pub is_decoy: bool,
+    /// Delay between moves to another slot, if the dummy moves
+    pub move_interval_ms: Option<u64>,
+    /// Applied to the dummy material
+    pub tint: Color,
+    /// Spawn weights at the lowest and highest difficulties
+    pub spawn_weights: (f32, f32),
+}
+impl DummyArchetype {
+    fn spawn_weight(&self, difficulty_0_1: f32) -> f32 {
+        self.spawn_weights.0 + (self.spawn_weights.1 - self.spawn_weights.0) * difficulty_0_1
+    }
+}
+
+/// Randomly picks a dummy kind, weighted by the current difficulty
+pub fn pick_dummy_kind<R: Rng + ?Sized>(rng: &mut R, difficulty_0_1: f32) -> DummyKind {
+    let total_weight: f32 = DUMMY_ARCHETYPES
+        .iter()
+        .map(|archetype| archetype.spawn_weight(difficulty_0_1))
+        .sum();
+    let mut pick = rng.gen_range(0.0..total_weight);
+    for archetype in DUMMY_ARCHETYPES.iter() {
+        pick -= archetype.spawn_weight(difficulty_0_1);
+        if pick <= 0. {
+            return archetype.kind;
+        }
+    }
+    DummyKind::Regular
+}
+
 #[derive(Event, Debug)]
 pub struct SpawnDummy {
     pub pos: Vec3,
     pub slot_index: usize,
+    pub kind: DummyKind,
     pub scope: ArenaMode,
 }
 
@@ -42,12 +157,21 @@ pub struct SpawnDummy {
 #[reflect(Component)]
 pub struct Dummy {
     pub slot_index: usize,
+    pub kind: DummyKind,
     pub despawn_timer: Timer,
 }
 
+/// Moves the dummy to another free slot each time the timer finishes
+#[derive(Component, Debug, Clone, PartialEq, Eq, Default, Reflect)]
+pub struct DummyMover {
+    pub move_timer: Timer,
+}
+
 #[derive(Resource)]
 pub struct DummyCachedData {
     pub collider: Collider,
+    /// Tinted materials for each dummy kind
+    pub materials: HashMap<DummyKind, Handle<StandardMaterial>>,
 }
 
 fn spawn_dummy(
@@ -68,9 +192,11 @@ fn spawn_dummy(
         return;
     };
     let mesh_handle = &gltf_mesh.primitives[0].mesh;
-    let mat_handle = &gltf.materials[0];
-
     let spawn_info = trigger.event();
+    let mat_handle = dummy_cached_data
+        .materials
+        .get(&spawn_info.kind)
+        .unwrap_or(&gltf.materials[0]);
 
     let fall_animation = Tween::new(
         EaseFunction::ExponentialIn,
@@ -113,6 +239,7 @@ fn spawn_dummy(
                 TimerMode::Once,
             ),
             slot_index: spawn_info.slot_index,
+            kind: spawn_info.kind,
         },
         // Animation
         Animator::new(fall_animation),
@@ -123,32 +250,54 @@ fn spawn_dummy(
 struct AttachDummyLogic {
     timer: Timer,
     slot_index: usize,
+    kind: DummyKind,
 }
 
 fn attach_dummy_logic(
     mut commands: Commands,
     time: Res<Time>,
     difficulty: Res<Difficulty>,
+    dummies_mode: Option<Res<DummiesModeData>>,
     mut spawning_dummies_query: Query<(Entity, &mut AttachDummyLogic)>,
 ) {
     for (entity, mut spawning_dummy) in spawning_dummies_query.iter_mut() {
         spawning_dummy.timer.tick(time.delta());
         if spawning_dummy.timer.finished() {
-            let despawn_delay = INITIAL_DUMMY_DESPAWN_TIMER_MS
-                - (difficulty.difficulty_factor_0_1() * FINAL_DUMMY_DESPAWN_TIMER_MS as f32) as u64;
-            commands
-                .entity(entity)
-                .remove::<AttachDummyLogic>()
-                .insert((
-                    Sliceable,
-                    Dummy {
-                        slot_index: spawning_dummy.slot_index,
-                        despawn_timer: Timer::new(
-                            Duration::from_millis(despawn_delay),
-                            TimerMode::Once,
-                        ),
-                    },
-                ));
+            let archetype = spawning_dummy.kind.archetype();
+            let despawn_delay = dummies_mode
+                .as_ref()
+                .and_then(|dummies_mode| dummies_mode.dummy_lifetime_ms)
+                .unwrap_or(
+                    INITIAL_DUMMY_DESPAWN_TIMER_MS
+                        - (difficulty.difficulty_factor_0_1() * FINAL_DUMMY_DESPAWN_TIMER_MS as f32)
+                            as u64,
+                );
+            let despawn_delay = (despawn_delay as f32 * archetype.lifetime_factor) as u64;
+            let mut dummy_commands = commands.entity(entity);
+            dummy_commands.remove::<AttachDummyLogic>().insert((
+                Sliceable,
+                Dummy {
+                    slot_index: spawning_dummy.slot_index,
+                    kind: spawning_dummy.kind,
+                    despawn_timer: Timer::new(
+                        Duration::from_millis(despawn_delay),
+                        TimerMode::Once,
+                    ),
+                },
+            ));
+            if archetype.slices_to_kill > 1 {
+                dummy_commands.insert(SliceArmor {
+                    remaining: archetype.slices_to_kill - 1,
+                });
+            }
+            if let Some(move_interval_ms) = archetype.move_interval_ms {
+                dummy_commands.insert(DummyMover {
+                    move_timer: Timer::new(
+                        Duration::from_millis(move_interval_ms),
+                        TimerMode::Repeating,
+                    ),
+                });
+            }
         }
     }
 }
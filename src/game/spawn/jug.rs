@@ -1,17 +1,21 @@
 use bevy::{gltf::GltfMesh, prelude::*};
 use bevy_rapier3d::prelude::{
-    ActiveCollisionTypes, ActiveEvents, Collider, ColliderMassProperties, ExternalImpulse,
-    Friction, Restitution, RigidBody,
+    ActiveCollisionTypes, ActiveEvents, Collider, ColliderMassProperties, Friction,
+    RapierConfiguration, Restitution, RigidBody, Velocity,
 };
+use rand::Rng;
 
 use crate::game::{
     arena::ArenaMode,
     assets::{GltfKey, HandleMap, ASSETS_SCALE},
+    ballistics::ballistic_launch_velocity,
+    breakable::Breakable,
 };
 
-use super::arena::DEFAULT_GLADIATOR_POS;
-
-pub const JUG_THROW_IMPULSE_FACTOR: f32 = 20.;
+/// Max spin given to a thrown jug, in rad/s
+pub const JUG_MAX_ANGULAR_VELOCITY: f32 = 4.;
+/// Contact force needed to break a jug
+pub const JUG_BREAK_MIN_FORCE: f32 = 60.;
 
 pub(super) fn plugin(app: &mut App) {
     app.observe(spawn_jug);
@@ -21,7 +25,10 @@ pub(super) fn plugin(app: &mut App) {
 #[derive(Event, Debug)]
 pub struct SpawnJug {
     pub pos: Vec3,
+    /// Point the jug will go through after `flight_time`
     pub target: Vec3,
+    /// Flight time in seconds
+    pub flight_time: f32,
     pub scope: ArenaMode,
 }
 
@@ -38,6 +45,7 @@ fn spawn_jug(
     trigger: Trigger<SpawnJug>,
     mut commands: Commands,
     jug_cached_data: Res<JugCachedData>,
+    rapier_config: Res<RapierConfiguration>,
     gltf_handles: Res<HandleMap<GltfKey>>,
     assets_gltf: Res<Assets<Gltf>>,
     assets_gltfmesh: Res<Assets<GltfMesh>>,
@@ -54,41 +62,48 @@ fn spawn_jug(
     let mat_handle = &gltf.materials[0];
 
     let jug_throw = trigger.event();
-    let jug_entity = commands
-        .spawn((
-            Name::new("Jug"),
-            StateScoped(trigger.event().scope),
-            PbrBundle {
-                mesh: mesh_handle.clone(),
-                material: mat_handle.clone(),
-                // TODO Gladiator height constant
-                transform: Transform::from_translation(jug_throw.pos + 3. * Vec3::Y)
-                    .looking_at(DEFAULT_GLADIATOR_POS, Vec3::Y)
-                    .with_scale(Vec3::splat(ASSETS_SCALE)),
-                ..default()
-            },
-            // Physic
-            RigidBody::Dynamic,
-            // TODO For now colliders are shared. Could have a simpler capsule collider or each have their own collider from mesh.
-            jug_cached_data.collider.clone(),
-            ActiveCollisionTypes::default(),
-            Friction::coefficient(0.7),
-            Restitution::coefficient(0.05),
-            ColliderMassProperties::Density(2.0),
-            ActiveEvents::COLLISION_EVENTS,
-            // Logic
-            Jug,
-        ))
-        // // TODO Could share the observer between all the jugs
-        // .observe(shatter_jug)
-        .id();
+    let linvel = ballistic_launch_velocity(
+        jug_throw.pos,
+        jug_throw.target,
+        jug_throw.flight_time,
+        rapier_config.gravity,
+    );
+    let mut rng = rand::thread_rng();
+    let angvel = Vec3::new(
+        rng.gen_range(-JUG_MAX_ANGULAR_VELOCITY..JUG_MAX_ANGULAR_VELOCITY),
+        rng.gen_range(-JUG_MAX_ANGULAR_VELOCITY..JUG_MAX_ANGULAR_VELOCITY),
+        rng.gen_range(-JUG_MAX_ANGULAR_VELOCITY..JUG_MAX_ANGULAR_VELOCITY),
+    );
 
-    let impulse = JUG_THROW_IMPULSE_FACTOR * (jug_throw.target - jug_throw.pos).normalize();
-    // TODO Impulse/force/kinematic movmeent to target
-    commands.entity(jug_entity).insert(ExternalImpulse {
-        impulse,
-        torque_impulse: Vec3::ZERO,
-    });
+    commands.spawn((
+        Name::new("Jug"),
+        StateScoped(trigger.event().scope),
+        PbrBundle {
+            mesh: mesh_handle.clone(),
+            material: mat_handle.clone(),
+            transform: Transform::from_translation(jug_throw.pos)
+                .looking_to(linvel, Vec3::Y)
+                .with_scale(Vec3::splat(ASSETS_SCALE)),
+            ..default()
+        },
+        // Physic
+        RigidBody::Dynamic,
+        // TODO For now colliders are shared. Could have a simpler capsule collider or each have their own collider from mesh.
+        jug_cached_data.collider.clone(),
+        ActiveCollisionTypes::default(),
+        Friction::coefficient(0.7),
+        Restitution::coefficient(0.05),
+        ColliderMassProperties::Density(2.0),
+        ActiveEvents::COLLISION_EVENTS,
+        Velocity { linvel, angvel },
+        // Logic
+        Jug,
+        // Stray jugs break on the arena, not when they roll on it
+        Breakable {
+            min_force: JUG_BREAK_MIN_FORCE,
+            ..default()
+        },
+    ));
 }
 
 // #[derive(Event, Debug)]
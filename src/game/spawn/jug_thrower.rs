@@ -1,15 +1,47 @@
-use bevy::prelude::*;
+use std::{f32::consts::FRAC_PI_2, time::Duration};
+
+use bevy::{
+    color::palettes::css::ORANGE_RED,
+    ecs::component::{ComponentHooks, StorageType},
+    prelude::*,
+};
+use bevy_rapier3d::prelude::Velocity;
+use bevy_tweening::{
+    lens::TransformScaleLens, Animator, EaseFunction, RepeatCount, RepeatStrategy, Tween,
+};
 
 use crate::game::{
     arena::ArenaMode,
-    assets::{HandleMap, SceneKey, GLADIATOR_ASSETS_SCALE},
-    shield::throwers::Thrower,
+    assets::{AnimationKey, HandleMap, SceneKey, GLADIATOR_ASSETS_SCALE},
+    ballistics::lead_target,
+    player_animation::{find_child_with_name_containing, PlayerAnimations, RIGHT_HAND_SLOT},
+    shield::throwers::{ThrowTarget, Thrower, ThrowersData},
 };
 
-use super::jug::SpawnJug;
+use super::{jug::SpawnJug, player::Player};
+
+/// Height at which the jug leaves the thrower if its hand can't be found
+pub const JUG_RELEASE_HEIGHT: f32 = 3.;
+
+pub const THROWER_THROW_ANIMATION_SPEED: f32 = 1.6;
+/// Fraction of the throw clip at which the jug leaves the hand
+pub const THROWER_RELEASE_CLIP_RATIO: f32 = 0.55;
+/// Releases the jug anyway if the throw animation could not be played
+pub const THROWER_RELEASE_FALLBACK_DELAY_MS: u64 = 900;
+
+pub const THROW_TELEGRAPH_RADIUS: f32 = 2.2;
+pub const THROW_TELEGRAPH_PULSE_MS: u64 = 220;
+pub const THROW_TELEGRAPH_COLOR: Color = Color::Srgba(ORANGE_RED);
 
 pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<ThrowTelegraphAssets>();
+
     app.observe(spawn_jug_thrower);
+
+    app.add_systems(
+        Update,
+        release_thrown_jugs.run_if(in_state(ArenaMode::Shield)),
+    );
 }
 
 #[derive(Event, Debug)]
@@ -19,6 +51,29 @@ pub struct SpawnJugThrower {
     pub scope: ArenaMode,
 }
 
+#[derive(Resource)]
+struct ThrowTelegraphAssets {
+    mesh: Handle<Mesh>,
+    material: Handle<StandardMaterial>,
+}
+
+impl FromWorld for ThrowTelegraphAssets {
+    fn from_world(world: &mut World) -> Self {
+        let mesh = world
+            .resource_mut::<Assets<Mesh>>()
+            .add(Circle::new(THROW_TELEGRAPH_RADIUS));
+        let material = world
+            .resource_mut::<Assets<StandardMaterial>>()
+            .add(StandardMaterial {
+                base_color: THROW_TELEGRAPH_COLOR.with_alpha(0.6),
+                unlit: true,
+                alpha_mode: AlphaMode::Blend,
+                ..default()
+            });
+        Self { mesh, material }
+    }
+}
+
 fn spawn_jug_thrower(
     trigger: Trigger<SpawnJugThrower>,
     mut commands: Commands,
@@ -42,25 +97,177 @@ fn spawn_jug_thrower(
         .observe(throw_jug);
 }
 
-#[derive(Event, Debug)]
+#[derive(Event, Debug, Clone)]
 pub struct ThrowJug {
-    pub at: Vec3,
+    pub aim: ThrowTarget,
+    /// Flight time in seconds
+    pub flight_time: f32,
+    /// Aim where the target will be at the end of the flight, instead of where it is now
+    pub lead_target: bool,
     pub scope: ArenaMode,
 }
 
+/// A thrower playing its throw animation. The jug is spawned at the release frame.
+#[derive(Debug)]
+pub struct ThrowWindUp {
+    throw: ThrowJug,
+    telegraph: Entity,
+    released: bool,
+    fallback_timer: Timer,
+}
+
+impl Component for ThrowWindUp {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+    fn register_component_hooks(hooks: &mut ComponentHooks) {
+        // Also frees the throwers despawned mid-wind-up
+        hooks.on_remove(|mut world, entity, _comp_id| {
+            if let Some(mut throwers_data) = world.get_resource_mut::<ThrowersData>() {
+                throwers_data.set_free(entity);
+            }
+        });
+    }
+}
+
 fn throw_jug(
     trigger: Trigger<ThrowJug>,
     mut commands: Commands,
+    animations: Res<PlayerAnimations>,
+    telegraph_assets: Res<ThrowTelegraphAssets>,
     transforms_query: Query<&Transform, With<Thrower>>,
+    children_query: Query<&Children>,
+    mut anim_players_query: Query<(&mut AnimationPlayer, &mut AnimationTransitions)>,
 ) {
     let throw_info = trigger.event();
+    let thrower_entity = trigger.entity();
 
-    let Ok(thrower_transform) = transforms_query.get(trigger.entity()) else {
+    let Ok(thrower_transform) = transforms_query.get(thrower_entity) else {
         return;
     };
-    commands.trigger(SpawnJug {
-        pos: thrower_transform.translation,
-        target: throw_info.at,
-        scope: throw_info.scope,
+
+    // Wind-up
+    for descendant in children_query.iter_descendants(thrower_entity) {
+        let Ok((mut animation_player, mut transitions)) = anim_players_query.get_mut(descendant)
+        else {
+            continue;
+        };
+        transitions
+            .play(
+                &mut animation_player,
+                animations.throw_anim,
+                Duration::from_millis(50),
+            )
+            .set_speed(THROWER_THROW_ANIMATION_SPEED);
+        break;
+    }
+
+    // Telegraph
+    let pulse = Tween::new(
+        EaseFunction::SineInOut,
+        Duration::from_millis(THROW_TELEGRAPH_PULSE_MS),
+        TransformScaleLens {
+            start: Vec3::splat(0.7),
+            end: Vec3::ONE,
+        },
+    )
+    .with_repeat_count(RepeatCount::Infinite)
+    .with_repeat_strategy(RepeatStrategy::MirroredRepeat);
+    let telegraph = commands
+        .spawn((
+            Name::new("Throw telegraph"),
+            StateScoped(throw_info.scope),
+            PbrBundle {
+                mesh: telegraph_assets.mesh.clone(),
+                material: telegraph_assets.material.clone(),
+                transform: Transform::from_translation(
+                    thrower_transform.translation + 0.05 * Vec3::Y,
+                )
+                .with_rotation(Quat::from_rotation_x(-FRAC_PI_2)),
+                ..default()
+            },
+            Animator::new(pulse),
+        ))
+        .id();
+
+    commands.entity(thrower_entity).insert(ThrowWindUp {
+        throw: throw_info.clone(),
+        telegraph,
+        released: false,
+        fallback_timer: Timer::new(
+            Duration::from_millis(THROWER_RELEASE_FALLBACK_DELAY_MS),
+            TimerMode::Once,
+        ),
     });
 }
+
+fn release_thrown_jugs(
+    mut commands: Commands,
+    time: Res<Time>,
+    animations: Res<PlayerAnimations>,
+    anim_handles: Res<HandleMap<AnimationKey>>,
+    animation_clips: Res<Assets<AnimationClip>>,
+    mut throwers_query: Query<(Entity, &Transform, &mut ThrowWindUp), Without<Player>>,
+    player_query: Query<(&Transform, Option<&Velocity>), With<Player>>,
+    children_query: Query<&Children>,
+    names_query: Query<&Name>,
+    anim_players_query: Query<&AnimationPlayer>,
+    global_transforms: Query<&GlobalTransform>,
+) {
+    let release_time = animation_clips
+        .get(&anim_handles[&AnimationKey::GladiatorThrow])
+        .map(|clip| clip.duration() * THROWER_RELEASE_CLIP_RATIO);
+
+    for (thrower_entity, thrower_transform, mut wind_up) in throwers_query.iter_mut() {
+        wind_up.fallback_timer.tick(time.delta());
+
+        let throw_animation = children_query
+            .iter_descendants(thrower_entity)
+            .find_map(|descendant| anim_players_query.get(descendant).ok())
+            .and_then(|animation_player| animation_player.animation(animations.throw_anim));
+
+        let (release_reached, animation_finished) = match (throw_animation, release_time) {
+            (Some(animation), Some(release_time)) => (
+                animation.seek_time() >= release_time || animation.is_finished(),
+                animation.is_finished(),
+            ),
+            _ => (
+                wind_up.fallback_timer.finished(),
+                wind_up.fallback_timer.finished(),
+            ),
+        };
+
+        if !wind_up.released && release_reached {
+            wind_up.released = true;
+            commands.entity(wind_up.telegraph).despawn_recursive();
+
+            let Ok((player_transform, player_velocity)) = player_query.get_single() else {
+                continue;
+            };
+            let throw_info = &wind_up.throw;
+            let mut target = player_transform.translation + throw_info.aim.height() * Vec3::Y;
+            if let (true, Some(velocity)) = (throw_info.lead_target, player_velocity) {
+                target = lead_target(target, velocity.linvel, throw_info.flight_time);
+            }
+
+            let release_pos = find_child_with_name_containing(
+                thrower_entity,
+                RIGHT_HAND_SLOT,
+                &names_query,
+                &children_query,
+            )
+            .and_then(|hand| global_transforms.get(hand).ok())
+            .map(|hand_transform| hand_transform.translation())
+            .unwrap_or(thrower_transform.translation + JUG_RELEASE_HEIGHT * Vec3::Y);
+
+            commands.trigger(SpawnJug {
+                pos: release_pos,
+                target,
+                flight_time: throw_info.flight_time,
+                scope: throw_info.scope,
+            });
+        }
+
+        if wind_up.released && animation_finished {
+            commands.entity(thrower_entity).remove::<ThrowWindUp>();
+        }
+    }
+}
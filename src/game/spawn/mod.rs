@@ -10,6 +10,7 @@ pub mod helmet;
 pub mod jug;
 pub mod jug_thrower;
 pub mod player;
+pub mod prop;
 pub mod shield;
 pub mod sword;
 
@@ -23,5 +24,6 @@ pub(super) fn plugin(app: &mut App) {
         dummy::plugin,
         jug_thrower::plugin,
         jug::plugin,
+        prop::plugin,
     ));
 }
@@ -0,0 +1,256 @@
+//! Destructible props placed around the arena.
+
+use bevy::{
+    color::palettes::css::{DARK_RED, PERU, SLATE_GRAY},
+    prelude::*,
+    utils::HashMap,
+};
+use bevy_rapier3d::prelude::{ActiveCollisionTypes, Collider, Friction, RigidBody};
+
+use crate::{
+    game::{
+        breakable::{Breakable, FractureMode},
+        score::{ScoreAction, ScoreActionType},
+        shattering::ShatterEntity,
+        spawn::arena::SpawnArena,
+    },
+    screen::Screen,
+};
+
+/// Empties of the arena glTF whose name starts with this are replaced by props
+pub const PROP_SPAWN_POINT_PREFIX: &str = "Prop.";
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<ArenaProp>();
+    app.register_type::<PropBroken>();
+    app.init_resource::<PropSpawnPoints>();
+    app.init_resource::<PropCachedData>();
+
+    app.add_systems(Update, record_prop_spawn_points);
+    app.add_systems(OnEnter(Screen::Playing), spawn_arena_props);
+
+    app.observe(clear_prop_spawn_points);
+    app.observe(spawn_prop);
+    app.observe(break_prop);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum PropKind {
+    Crate,
+    Banner,
+    WeaponRack,
+}
+
+impl PropKind {
+    pub fn archetype(&self) -> &'static PropArchetype {
+        PROP_ARCHETYPES
+            .iter()
+            .find(|archetype| archetype.kind == *self)
+            .unwrap()
+    }
+
+    /// Kind of prop placed by an empty named like "Prop.Crate.001"
+    pub fn from_spawn_point_name(name: &str) -> Option<Self> {
+        let name = name.strip_prefix(PROP_SPAWN_POINT_PREFIX)?;
+        PROP_ARCHETYPES
+            .iter()
+            .find(|archetype| name.starts_with(archetype.name))
+            .map(|archetype| archetype.kind)
+    }
+}
+
+pub struct PropArchetype {
+    pub kind: PropKind,
+    /// Name of the spawn points in the arena glTF, after [`PROP_SPAWN_POINT_PREFIX`]
+    pub name: &'static str,
+    pub size: Vec3,
+    pub color: Color,
+    pub health: f32,
+    pub min_break_force: f32,
+    pub fracture_mode: FractureMode,
+    pub shard_count: Option<u32>,
+}
+
+pub const PROP_ARCHETYPES: [PropArchetype; 3] = [
+    PropArchetype {
+        kind: PropKind::Crate,
+        name: "Crate",
+        size: Vec3::new(0.9, 0.9, 0.9),
+        color: Color::Srgba(PERU),
+        health: 1.,
+        min_break_force: 30.,
+        fracture_mode: FractureMode::Shatter,
+        shard_count: Some(5),
+    },
+    PropArchetype {
+        kind: PropKind::Banner,
+        name: "Banner",
+        size: Vec3::new(1.2, 2.8, 0.08),
+        color: Color::Srgba(DARK_RED),
+        health: 0.5,
+        min_break_force: 15.,
+        fracture_mode: FractureMode::Slice,
+        shard_count: None,
+    },
+    PropArchetype {
+        kind: PropKind::WeaponRack,
+        name: "WeaponRack",
+        size: Vec3::new(1.6, 1.3, 0.4),
+        color: Color::Srgba(SLATE_GRAY),
+        health: 2.,
+        min_break_force: 40.,
+        fracture_mode: FractureMode::Shatter,
+        shard_count: Some(3),
+    },
+];
+
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+pub struct ArenaProp {
+    pub kind: PropKind,
+}
+
+/// A prop was broken. Only sent once per prop.
+#[derive(Event, Debug, Clone, Reflect)]
+pub struct PropBroken {
+    pub kind: PropKind,
+    pub pos: Vec3,
+}
+
+#[derive(Event, Debug)]
+pub struct SpawnProp {
+    pub kind: PropKind,
+    /// Position of the base of the prop
+    pub transform: Transform,
+}
+
+/// Where the props go, read from the arena scene once it is spawned
+#[derive(Resource, Debug, Default)]
+pub struct PropSpawnPoints {
+    /// By spawn point entity, so that a point seen twice is only recorded once
+    pub points: HashMap<Entity, (PropKind, Transform)>,
+}
+
+#[derive(Resource)]
+struct PropCachedData {
+    meshes: HashMap<PropKind, Handle<Mesh>>,
+    materials: HashMap<PropKind, Handle<StandardMaterial>>,
+}
+
+impl FromWorld for PropCachedData {
+    fn from_world(world: &mut World) -> Self {
+        let mut meshes = HashMap::new();
+        let mut materials = HashMap::new();
+        for archetype in PROP_ARCHETYPES.iter() {
+            let size = archetype.size;
+            meshes.insert(
+                archetype.kind,
+                world
+                    .resource_mut::<Assets<Mesh>>()
+                    .add(Cuboid::new(size.x, size.y, size.z)),
+            );
+            materials.insert(
+                archetype.kind,
+                world
+                    .resource_mut::<Assets<StandardMaterial>>()
+                    .add(archetype.color),
+            );
+        }
+        Self { meshes, materials }
+    }
+}
+
+/// Scene entities are spawned after `Update`, their `GlobalTransform` is ready once they are seen here
+fn record_prop_spawn_points(
+    mut spawn_points: ResMut<PropSpawnPoints>,
+    names_query: Query<(Entity, &Name, &GlobalTransform), Added<Name>>,
+) {
+    for (entity, name, global_transform) in names_query.iter() {
+        let Some(kind) = PropKind::from_spawn_point_name(name.as_str()) else {
+            continue;
+        };
+        let (_scale, rotation, translation) = global_transform.to_scale_rotation_translation();
+        spawn_points.points.insert(
+            entity,
+            (
+                kind,
+                Transform::from_translation(translation).with_rotation(rotation),
+            ),
+        );
+    }
+}
+
+/// The spawn points of a previous arena scene are gone with it
+fn clear_prop_spawn_points(
+    _trigger: Trigger<SpawnArena>,
+    mut spawn_points: ResMut<PropSpawnPoints>,
+) {
+    spawn_points.points.clear();
+}
+
+fn spawn_arena_props(mut commands: Commands, spawn_points: Res<PropSpawnPoints>) {
+    for (kind, transform) in spawn_points.points.values() {
+        commands.trigger(SpawnProp {
+            kind: *kind,
+            transform: *transform,
+        });
+    }
+}
+
+fn spawn_prop(
+    trigger: Trigger<SpawnProp>,
+    mut commands: Commands,
+    prop_cached_data: Res<PropCachedData>,
+) {
+    let spawn_info = trigger.event();
+    let archetype = spawn_info.kind.archetype();
+    let half_size = archetype.size / 2.;
+
+    commands.spawn((
+        Name::new(archetype.name),
+        StateScoped(Screen::Playing),
+        PbrBundle {
+            mesh: prop_cached_data.meshes[&spawn_info.kind].clone(),
+            material: prop_cached_data.materials[&spawn_info.kind].clone(),
+            transform: spawn_info
+                .transform
+                .with_translation(spawn_info.transform.translation + half_size.y * Vec3::Y),
+            ..default()
+        },
+        // Physics
+        RigidBody::Fixed,
+        Collider::cuboid(half_size.x, half_size.y, half_size.z),
+        ActiveCollisionTypes::default(),
+        Friction::coefficient(0.7),
+        // Logic
+        ArenaProp {
+            kind: spawn_info.kind,
+        },
+        Breakable {
+            health: archetype.health,
+            min_force: archetype.min_break_force,
+            mode: archetype.fracture_mode,
+            shard_count: archetype.shard_count,
+            ..default()
+        },
+    ));
+}
+
+fn break_prop(
+    trigger: Trigger<ShatterEntity>,
+    mut commands: Commands,
+    props_query: Query<(&ArenaProp, &Transform)>,
+) {
+    let entity = trigger.event().entity;
+    let Ok((prop, transform)) = props_query.get(entity) else {
+        return;
+    };
+    commands.entity(entity).remove::<ArenaProp>();
+    commands.trigger(ScoreAction {
+        action: ScoreActionType::Prop,
+        pos: transform.translation,
+    });
+    commands.trigger(PropBroken {
+        kind: prop.kind,
+        pos: transform.translation,
+    });
+}
@@ -1,6 +1,6 @@
 use bevy::prelude::*;
 use bevy_rapier3d::prelude::{
-    ActiveCollisionTypes, Collider, ColliderMassProperties, Friction, Restitution,
+    ActiveCollisionTypes, Collider, ColliderMassProperties, Friction, Restitution, RigidBody,
 };
 
 use crate::game::{
@@ -41,8 +41,8 @@ fn spawn_shield(
             transform: Transform::IDENTITY,
             ..default()
         },
-        // Physic
-        // RigidBody::KinematicPositionBased,
+        // Physic. Attached to the forearm, the body follows the animated arm and hits the jugs with its velocity
+        RigidBody::KinematicPositionBased,
         cached_data.collider.clone(),
         ActiveCollisionTypes::default(),
         Friction::coefficient(0.7),
@@ -1,10 +1,9 @@
 use bevy::{
     app::App,
-    math::Vec3,
-    prelude::{Event, Query, Trigger},
+    prelude::{Commands, Event, Trigger},
 };
 
-use crate::game::camera::{PanOrbitSettings, PanOrbitState};
+use crate::game::camera_director::{CameraPresetKey, SetCameraPreset};
 
 #[derive(Event, Debug)]
 pub struct SetSwordModeCamera;
@@ -13,20 +12,6 @@ pub(super) fn plugin(app: &mut App) {
     app.observe(setup_camera);
 }
 
-pub fn setup_camera(
-    _trigger: Trigger<SetSwordModeCamera>,
-    mut camera_query: Query<(&mut PanOrbitState, &mut PanOrbitSettings)>,
-) {
-    let Ok((mut cam_state, mut cam_settings)) = camera_query.get_single_mut() else {
-        return;
-    };
-    cam_state.center = Vec3::new(-0.18647689, 4.443227, 1.4876647);
-    cam_state.radius = 19.983498;
-    cam_state.pitch = -0.3036932;
-    cam_state.yaw = 3.1415827;
-    cam_state.needs_transform_refresh = true;
-
-    cam_settings.auto_orbit = false;
+pub fn setup_camera(_trigger: Trigger<SetSwordModeCamera>, mut commands: Commands) {
+    commands.trigger(SetCameraPreset(CameraPresetKey::Sword));
 }
-
-// TODO handle cam movements with mouse
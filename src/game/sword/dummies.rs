@@ -5,25 +5,27 @@ use bevy::{
     color::palettes::css::{GREEN, RED},
     ecs::component::StorageType,
     math::Vec3,
+    pbr::StandardMaterial,
     prelude::{
         in_state, Children, Commands, Component, DespawnRecursiveExt, Entity, Event, Gizmos,
-        IntoSystemConfigs, Query, Res, ResMut, Resource, StateScoped, Transform, Trigger, With,
-        Without,
+        Handle, IntoSystemConfigs, Query, Res, ResMut, Resource, StateScoped, Transform, Trigger,
+        With, Without,
     },
     reflect::Reflect,
     time::{Time, Timer, TimerMode},
     utils::default,
 };
+use bevy_tweening::{lens::TransformPositionLens, Animator, EaseFunction, Tween};
 use rand::Rng;
 
 use crate::game::{
     arena::ArenaMode,
     cycle::Cycle,
-    score::{ScoreAction, ScoreActionType},
-    spawn::dummy::{Dummy, SpawnDummy},
+    score::{Difficulty, ScoreAction, ScoreActionType},
+    spawn::dummy::{pick_dummy_kind, Dummy, DummyCachedData, DummyKind, DummyMover, SpawnDummy},
 };
 
-use super::slicing::SliceEvent;
+use super::slicing::{SliceArmorHit, SliceEvent};
 
 pub const DUMMY_POSITIONS: [Vec3; 6] = [
     Vec3::new(4., 0., 1.),
@@ -41,6 +43,8 @@ pub const DUMMIES_SPAWN_INTERVAL_MIN_MS: u64 = 550;
 pub const DUMMIES_SPAWN_INTERVAL_MAX_MS: u64 = 1450;
 pub const MAX_DUMMIES_COUNT: usize = 4;
 
+pub const DUMMY_MOVE_DURATION_MS: u64 = 350;
+
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<DummiesModeData>();
 
@@ -48,12 +52,19 @@ pub(super) fn plugin(app: &mut App) {
     // app.add_systems(OnExit(ArenaMode::Sword), on_exit_sword_mode);
     app.add_systems(
         Update,
-        (free_killed_dummies_slots, spawn_dummies, despawn_dummies)
+        (
+            free_killed_dummies_slots,
+            free_vacated_dummy_slots,
+            spawn_dummies,
+            move_dummies,
+            despawn_dummies,
+        )
             .run_if(in_state(ArenaMode::Sword)),
     );
 
     app.observe(spawn_dummy_slots);
     app.observe(queue_dummy_slot_free);
+    app.observe(break_dummy_armor);
 }
 
 #[derive(Resource, Reflect)]
@@ -61,9 +72,15 @@ pub struct DummiesModeData {
     dummy_slots: Vec<Entity>,
     free_slot_indexes: Vec<usize>,
     killed_dummies_queue: Vec<(usize, Timer)>,
+    /// Slots left by the moving dummies, freed once they slid out of them
+    vacated_slots_queue: Vec<(usize, Timer)>,
 
     spawn_timer: Timer,
     max_dummy_count: usize,
+    /// Range of the random delay between two spawns
+    pub spawn_interval_ms: (u64, u64),
+    /// Replaces the lifetime given by the difficulty, before the dummy kind factor
+    pub dummy_lifetime_ms: Option<u64>,
 }
 
 impl Default for DummiesModeData {
@@ -77,6 +94,9 @@ impl Default for DummiesModeData {
             dummy_slots: default(),
             free_slot_indexes: default(),
             killed_dummies_queue: default(),
+            vacated_slots_queue: default(),
+            spawn_interval_ms: (DUMMIES_SPAWN_INTERVAL_MIN_MS, DUMMIES_SPAWN_INTERVAL_MAX_MS),
+            dummy_lifetime_ms: None,
         }
     }
 }
@@ -151,10 +171,23 @@ fn free_killed_dummies_slots(time: Res<Time>, mut dummies: ResMut<DummiesModeDat
     }
 }
 
+fn free_vacated_dummy_slots(time: Res<Time>, mut dummies: ResMut<DummiesModeData>) {
+    let dummies = &mut *dummies;
+    let free_slot_indexes = &mut dummies.free_slot_indexes;
+    dummies.vacated_slots_queue.retain_mut(|(slot, timer)| {
+        timer.tick(time.delta());
+        if timer.finished() {
+            free_slot_indexes.push(*slot);
+        }
+        !timer.finished()
+    });
+}
+
 fn spawn_dummies(
     mut commands: Commands,
     time: Res<Time>,
     cycle: Res<Cycle>,
+    difficulty: Res<Difficulty>,
     mut dummies_mode: ResMut<DummiesModeData>,
     dummy_slots_query: Query<&Transform, (With<DummySlot>, Without<Children>)>,
 ) {
@@ -175,11 +208,11 @@ fn spawn_dummies(
         commands.trigger(SpawnDummy {
             pos: slot.translation,
             slot_index: free_slot_index,
+            kind: pick_dummy_kind(&mut rng, difficulty.difficulty_factor_0_1()),
             scope: cycle.current_mode,
         });
-        let mut rng = rand::thread_rng();
-        let next_spawn_delay =
-            rng.gen_range(DUMMIES_SPAWN_INTERVAL_MIN_MS..DUMMIES_SPAWN_INTERVAL_MAX_MS);
+        let (min_interval_ms, max_interval_ms) = dummies_mode.spawn_interval_ms;
+        let next_spawn_delay = rng.gen_range(min_interval_ms..=max_interval_ms);
         dummies_mode
             .spawn_timer
             .set_duration(Duration::from_millis(next_spawn_delay));
@@ -199,15 +232,71 @@ fn despawn_dummies(
         if dummy.despawn_timer.finished() {
             // TODO Despawn this dummy with an animation
             commands.entity(entity).despawn_recursive();
-            commands.trigger(ScoreAction {
-                action: ScoreActionType::Bad,
-                pos: transform.translation,
-            });
+            // Ignoring a decoy is the right call
+            if !dummy.kind.archetype().is_decoy {
+                commands.trigger(ScoreAction {
+                    action: ScoreActionType::Bad,
+                    pos: transform.translation,
+                });
+            }
             dummies_mode_data.free_slot_indexes.push(dummy.slot_index);
         }
     }
 }
 
+fn move_dummies(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut dummies_mode: ResMut<DummiesModeData>,
+    mut movers_query: Query<(Entity, &Transform, &mut Dummy, &mut DummyMover)>,
+    dummy_slots_query: Query<&Transform, (With<DummySlot>, Without<Dummy>)>,
+) {
+    for (entity, transform, mut dummy, mut mover) in movers_query.iter_mut() {
+        mover.move_timer.tick(time.delta());
+        if !mover.move_timer.just_finished() || dummies_mode.free_slot_indexes.is_empty() {
+            continue;
+        }
+        let mut rng = rand::thread_rng();
+        let random_index = rng.gen_range(0..dummies_mode.free_slot_indexes.len());
+        let new_slot_index = dummies_mode.free_slot_indexes[random_index];
+        let Ok(slot) = dummy_slots_query.get(dummies_mode.dummy_slots[new_slot_index]) else {
+            continue;
+        };
+        dummies_mode.free_slot_indexes.swap_remove(random_index);
+        dummies_mode.vacated_slots_queue.push((
+            dummy.slot_index,
+            Timer::new(
+                Duration::from_millis(DUMMY_MOVE_DURATION_MS),
+                TimerMode::Once,
+            ),
+        ));
+        dummy.slot_index = new_slot_index;
+
+        let slide = Tween::new(
+            EaseFunction::QuadraticInOut,
+            Duration::from_millis(DUMMY_MOVE_DURATION_MS),
+            TransformPositionLens {
+                start: transform.translation,
+                end: slot.translation,
+            },
+        );
+        commands.entity(entity).insert(Animator::new(slide));
+    }
+}
+
+fn break_dummy_armor(
+    trigger: Trigger<SliceArmorHit>,
+    dummy_cached_data: Res<DummyCachedData>,
+    mut dummies_query: Query<&mut Handle<StandardMaterial>, With<Dummy>>,
+) {
+    let Ok(mut material) = dummies_query.get_mut(trigger.event().entity) else {
+        return;
+    };
+    if let Some(regular_material) = dummy_cached_data.materials.get(&DummyKind::Regular) {
+        *material = regular_material.clone();
+    }
+}
+
 pub fn debug_draw_dummy_slots(
     mut gizmos: Gizmos,
     dummies: Option<Res<DummiesModeData>>,
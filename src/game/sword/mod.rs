@@ -11,6 +11,7 @@ pub mod camera;
 pub mod dummies;
 pub mod scoring;
 pub mod slicing;
+pub mod trail;
 
 pub(super) fn plugin(app: &mut App) {
     app.add_plugins((
@@ -18,6 +19,7 @@ pub(super) fn plugin(app: &mut App) {
         dummies::plugin,
         scoring::plugin,
         camera::plugin,
+        trail::plugin,
     ));
     app.add_systems(OnEnter(ArenaMode::Sword), on_enter_sword_mode);
     app.add_systems(OnExit(ArenaMode::Sword), on_exit_sword_mode);
@@ -8,10 +8,11 @@ use crate::game::{
     spawn::dummy::Dummy,
 };
 
-use super::slicing::SliceEvent;
+use super::slicing::{MultiSliceEvent, SliceEvent, SlicedFragment};
 
 pub(super) fn plugin(app: &mut App) {
     app.observe(update_score);
+    app.observe(score_multi_slice);
 }
 
 pub fn update_score(
@@ -19,15 +20,35 @@ pub fn update_score(
     mut commands: Commands,
     // mut dummies: ResMut<Dummies>,
     dummies_query: Query<&Dummy>,
+    fragments_query: Query<&SlicedFragment>,
 ) {
     let slice_info = trigger.event();
 
-    if let Ok(_dummy) = dummies_query.get(slice_info.entity) {
+    if let Ok(dummy) = dummies_query.get(slice_info.entity) {
+        let action = if dummy.kind.archetype().is_decoy {
+            ScoreActionType::Bad
+        } else {
+            // Clean strokes are perfect hits, which can drop pickups
+            slice_info.quality
+        };
         commands.trigger(ScoreAction {
-            action: ScoreActionType::Good,
+            action,
             pos: slice_info.pos,
         });
-
-        //TODO != ScoreActionType based on the dummy data
+    } else if let Ok(fragment) = fragments_query.get(slice_info.entity) {
+        if fragment.is_airborne() {
+            commands.trigger(ScoreAction {
+                action: ScoreActionType::Juggle,
+                pos: slice_info.pos,
+            });
+        }
     }
 }
+
+pub fn score_multi_slice(trigger: Trigger<MultiSliceEvent>, mut commands: Commands) {
+    let multi_slice = trigger.event();
+    commands.trigger(ScoreAction {
+        action: ScoreActionType::MultiSlice(multi_slice.count),
+        pos: multi_slice.pos,
+    });
+}
@@ -4,35 +4,42 @@ use bevy::{
     app::{App, Update},
     asset::{Assets, Handle},
     core::Name,
-    gltf::Gltf,
     input::ButtonInput,
-    math::{Vec3, Vec3A},
+    math::{Ray3d, Vec3, Vec3A},
     pbr::{PbrBundle, StandardMaterial},
     prelude::{
-        default, Camera, Commands, Component, DespawnRecursiveExt, Entity, Event, GlobalTransform,
-        IntoSystemConfigs, Mesh, MouseButton, Query, Res, ResMut, Resource, StateScoped, Transform,
-        TransformPoint, Trigger, With, Without,
+        default, not, BuildChildren, Camera, Commands, Component, DespawnRecursiveExt, Entity,
+        Event, EventReader, GlobalTransform, HierarchyQueryExt, IntoSystemConfigs, Mesh,
+        MouseButton, Parent, Query, Res, ResMut, Resource, Transform, TransformPoint, Trigger,
+        With, Without,
     },
     reflect::Reflect,
     time::{Time, Timer, TimerMode},
 };
 use bevy_ghx_destruction::{slicing::slicing::slice_bevy_mesh, types::Plane};
-use bevy_mod_raycast::{cursor::CursorRay, prelude::Raycast};
+use bevy_mod_raycast::cursor::CursorRay;
 use bevy_rapier3d::prelude::{
-    ActiveCollisionTypes, Collider, ColliderMassProperties, ComputedColliderShape, ExternalImpulse,
-    Friction, Restitution, RigidBody,
+    ActiveCollisionTypes, ActiveEvents, Collider, ColliderMassProperties, CollisionEvent,
+    ComputedColliderShape, ExternalImpulse, Friction, Restitution, RigidBody,
 };
 
 use crate::{
     game::{
-        assets::{GltfKey, HandleMap},
-        shattering::ShatterEntity,
+        breakable::Breakable,
+        debris::{spawn_debris_group, DebrisBudget, DebrisExpiry, DebrisPiece},
+        player_animation::{AnimationMarker, AnimationMarkerReached, SlashSwing},
+        player_camera::player_camera_dragging,
+        raycasting::FilteredRaycast,
+        score::ScoreActionType,
+        shattering::mesh_volume,
+        spawn::player::Player,
     },
-    screen::Screen,
+    ui::interaction::cursor_over_widget,
     AppSet,
 };
 
-pub const PLAYER_SLICE_FRAGMENTATION_DELAY_MS: u64 = 85;
+/// Fragmentation happens on the blade contact frame of the slash animation, or after this delay if the marker is never reached
+pub const PLAYER_SLICE_FRAGMENTATION_FALLBACK_DELAY_MS: u64 = 400;
 pub const SLICED_FRAGMENTS_SHATTER_DELAY_MS: u64 = 2000;
 
 /// Impulse to force the fragments appart, more satisfying
@@ -42,51 +49,101 @@ pub const FRAGMENTS_TORQUE_IMPULSE_FACTOR: f32 = 3.;
 
 pub const DUMMY_FRAGMENT_FRICTION: f32 = 1.;
 
+/// A slice is perfect if the stroke is long and straight enough
+pub const PERFECT_SLICE_MIN_LENGTH: f32 = 1.;
+/// Distance between the stroke ends divided by the distance travelled by the cursor
+pub const PERFECT_SLICE_MIN_STRAIGHTNESS: f32 = 0.92;
+
+/// Fragments smaller than this can't be sliced again
+pub const MIN_RESLICE_VOLUME: f32 = 0.15;
+/// Used when a sliced entity has no [`SliceDepth`]
+pub const DEFAULT_MAX_SLICE_DEPTH: u32 = 2;
+
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<Sliceable>();
+    app.register_type::<SliceArmor>();
+    app.register_type::<SliceArmorHit>();
+    app.register_type::<SliceDepth>();
     app.register_type::<SliceAttemptEvent>();
     app.register_type::<SliceEvent>();
+    app.register_type::<SlashStarted>();
+    app.register_type::<SlicePlaneEvent>();
+    app.register_type::<MultiSliceEvent>();
+    app.register_type::<StrokeTarget>();
     app.register_type::<SlicerState>();
+    app.register_type::<SliceSettings>();
     app.register_type::<FragmentationQueue>();
 
     app.add_systems(
         Update,
         (
-            detect_slices.in_set(AppSet::RecordInput),
-            (dequeue_fragmentations, shatter_fragments).in_set(AppSet::Update),
+            // The cursor moves with the camera while it is dragged, the strokes it would trace are not the player's.
+            // Neither are the clicks on the practice and tutorial widgets.
+            (
+                detect_slices.run_if(not(player_camera_dragging).and_then(not(cursor_over_widget))),
+                cancel_stroke.run_if(player_camera_dragging.or_else(cursor_over_widget)),
+            )
+                .in_set(AppSet::RecordInput),
+            (dequeue_fragmentations, detect_fragments_landing).in_set(AppSet::Update),
         ),
     );
     app.init_resource::<SlicerState>();
+    app.init_resource::<SliceSettings>();
     app.init_resource::<FragmentationQueue>();
+    app.init_resource::<StrokeResults>();
 
     app.observe(slice);
+    app.observe(end_stroke);
     app.observe(slice_entity);
+    app.observe(fragment_on_blade_contact);
 }
 
 #[derive(Component, Debug, Clone, PartialEq, Eq, Default, Reflect)]
 pub struct Sliceable;
 
+/// Absorbs slices before the entity can be cut
 #[derive(Component, Debug, Clone, PartialEq, Eq, Default, Reflect)]
-pub struct SlicedFragment {
-    shatter_timer: Timer,
+pub struct SliceArmor {
+    pub remaining: u32,
 }
-impl SlicedFragment {
-    pub fn new() -> Self {
+
+/// How many times an object has been sliced already, and how many times it can be
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub struct SliceDepth {
+    pub depth: u32,
+    pub max_depth: u32,
+}
+impl Default for SliceDepth {
+    fn default() -> Self {
         Self {
-            shatter_timer: Timer::new(
-                Duration::from_millis(SLICED_FRAGMENTS_SHATTER_DELAY_MS),
-                TimerMode::Once,
-            ),
+            depth: 0,
+            max_depth: DEFAULT_MAX_SLICE_DEPTH,
         }
     }
 }
 
+#[derive(Component, Debug, Clone, PartialEq, Eq, Default, Reflect)]
+pub struct SlicedFragment {
+    landed: bool,
+}
+impl SlicedFragment {
+    /// Did not touch anything since it was cut
+    pub fn is_airborne(&self) -> bool {
+        !self.landed
+    }
+}
+
 #[derive(Event, Debug, Clone, Reflect)]
 /// May not slice the entity, depending on the positions
 struct SliceAttemptEvent {
+    /// Where the stroke entered the entity
     pub begin: Vec3,
+    /// Where the stroke left the entity
     pub end: Vec3,
+    /// Ends of the whole stroke. With the camera position, they define the slice plane shared by all the targets.
+    pub stroke: (Vec3, Vec3),
     pub entity: Entity,
+    pub quality: ScoreActionType,
 }
 
 #[derive(Event, Debug, Clone, Reflect)]
@@ -94,149 +151,313 @@ struct SliceAttemptEvent {
 pub struct SliceEvent {
     pub entity: Entity,
     pub pos: Vec3,
+    /// Quality of the stroke that sliced the entity
+    pub quality: ScoreActionType,
+}
+
+#[derive(Event, Debug, Clone, Reflect)]
+/// A slice succeeded along this plane, in world space
+pub struct SlicePlaneEvent {
+    pub begin: Vec3,
+    pub end: Vec3,
+    pub normal: Vec3,
+}
+
+#[derive(Event, Debug, Clone, Copy, Reflect)]
+/// The first entity of a stroke is sliced, the player swings the blade once per stroke
+pub struct SlashStarted {
+    pub swing: u32,
+}
+
+#[derive(Event, Debug, Clone, Reflect)]
+/// All the slice attempts of a stroke were processed
+struct SliceStrokeEnded;
+
+#[derive(Event, Debug, Clone, Reflect)]
+/// A single stroke sliced more than one entity
+pub struct MultiSliceEvent {
+    pub count: u32,
+    pub pos: Vec3,
+}
+
+#[derive(Event, Debug, Clone, Reflect)]
+/// A slice was absorbed by a [`SliceArmor`]
+pub struct SliceArmorHit {
+    pub entity: Entity,
+    pub pos: Vec3,
 }
 
 #[derive(Event, Debug, Clone, Reflect)]
 struct SliceEntity {
     sliced_entity: Entity,
     sliced_object_transform: Transform,
+    sliced_object_material: Handle<StandardMaterial>,
+    slice_depth: SliceDepth,
     slice_positions: (Vec3, Vec3),
     fragments_meshes: [Mesh; 2],
 }
 
 #[derive(Resource, Default, Reflect)]
 struct FragmentationQueue {
-    queue: Vec<(SliceEntity, Timer)>,
+    queue: Vec<QueuedFragmentation>,
+    /// Incremented with each [`SlashStarted`]
+    swing: u32,
+}
+
+#[derive(Reflect)]
+struct QueuedFragmentation {
+    fragmentation: SliceEntity,
+    /// Swing that sliced the entity, its blade contact releases the fragments
+    swing: u32,
+    fallback_timer: Timer,
+}
+
+/// Positions of the entities sliced by the current stroke
+#[derive(Resource, Default, Reflect)]
+struct StrokeResults {
+    sliced_positions: Vec<Vec3>,
+}
+
+/// Part of a stroke crossing a single sliceable entity
+#[derive(Debug, Clone, Copy, Reflect)]
+pub struct StrokeTarget {
+    pub entity: Entity,
+    pub entry: Vec3,
+    pub exit: Vec3,
+    /// Distance travelled by the cursor on the target
+    pub travelled: f32,
+}
+impl StrokeTarget {
+    pub fn quality(&self) -> ScoreActionType {
+        slice_quality(self.entry, self.exit, self.travelled)
+    }
+}
+
+/// Can be changed by power-ups
+#[derive(Resource, Debug, Clone, Default, Reflect)]
+pub struct SliceSettings {
+    /// When not zero, the blade also cuts the objects this far from the cursor
+    pub blade_half_width: f32,
 }
 
 #[derive(Resource, Debug, Clone, Default, Reflect)]
-enum SlicerState {
+pub enum SlicerState {
     #[default]
     Idle,
-    NoTarget,
-    FirstHit(Vec3, Entity),
-    Slicing {
-        start: (Vec3, Entity),
-        last_hit: Vec3,
+    /// The mouse is held down
+    Stroke {
+        /// Every sliceable crossed by the stroke, in order
+        targets: Vec<StrokeTarget>,
+        /// Index of the target under the cursor
+        current: Option<usize>,
     },
 }
+impl SlicerState {
+    fn current_target(&self) -> Option<&StrokeTarget> {
+        match self {
+            SlicerState::Stroke {
+                targets,
+                current: Some(index),
+            } => targets.get(*index),
+            _ => None,
+        }
+    }
+
+    /// Last position of the cursor on a sliceable target
+    pub fn stroke_point(&self) -> Option<Vec3> {
+        self.current_target().map(|target| target.exit)
+    }
+
+    /// Quality the slice of the current target would have if released now
+    pub fn expected_quality(&self) -> Option<ScoreActionType> {
+        self.current_target()
+            .filter(|target| target.entry != target.exit)
+            .map(|target| target.quality())
+    }
+}
+
+pub fn slice_quality(begin: Vec3, end: Vec3, travelled: f32) -> ScoreActionType {
+    let length = (end - begin).length();
+    if travelled > 0.
+        && length >= PERFECT_SLICE_MIN_LENGTH
+        && length / travelled >= PERFECT_SLICE_MIN_STRAIGHTNESS
+    {
+        ScoreActionType::Perfect
+    } else {
+        ScoreActionType::Good
+    }
+}
 
 fn detect_slices(
     mut commands: Commands,
     mouse: Res<ButtonInput<MouseButton>>,
     cursor_ray: Res<CursorRay>,
+    slice_settings: Res<SliceSettings>,
     mut slicer_state: ResMut<SlicerState>,
-    mut raycast: Raycast,
-    // mut gizmos: Gizmos,
-    sliceables_query: Query<(), With<Sliceable>>,
+    mut raycast: FilteredRaycast,
 ) {
     let Some(cursor_ray) = cursor_ray.0 else {
         return;
     };
 
     if mouse.pressed(MouseButton::Left) {
-        // raycast.debug_cast_ray(cursor_ray, &default(), &mut gizmos);
-        let hits = raycast.cast_ray(cursor_ray, &default());
-        match *slicer_state {
-            SlicerState::Idle => {
-                if hits.is_empty() {
-                    *slicer_state = SlicerState::NoTarget;
-                } else {
-                    let hit = &hits[0];
-                    let sliceable = sliceables_query.get(hit.0);
-                    if sliceable.is_ok() {
-                        *slicer_state = SlicerState::FirstHit(hit.1.position(), hit.0);
-                    }
+        // Fragments, shards and vfx passing in front of a target don't block the blade
+        let hit = raycast.cast_sliceable(cursor_ray);
+
+        // A wide blade also cuts what is around the cursor
+        let mut blade_hits = Vec::new();
+        if slice_settings.blade_half_width > 0. {
+            let right = cursor_ray.direction.cross(Vec3::Y).normalize_or_zero();
+            let up = right.cross(*cursor_ray.direction);
+            for offset in [right, -right, up, -up] {
+                let ray = Ray3d {
+                    origin: cursor_ray.origin + offset * slice_settings.blade_half_width,
+                    direction: cursor_ray.direction,
+                };
+                if let Some(blade_hit) = raycast.cast_sliceable(ray) {
+                    blade_hits.push(blade_hit);
                 }
             }
-            SlicerState::NoTarget => {
-                if !hits.is_empty() {
-                    let hit = &hits[0];
-                    let sliceable = sliceables_query.get(hit.0);
-                    if sliceable.is_ok() {
-                        *slicer_state = SlicerState::FirstHit(hit.1.position(), hit.0);
-                    }
-                }
+        }
+
+        if matches!(*slicer_state, SlicerState::Idle) {
+            *slicer_state = SlicerState::Stroke {
+                targets: Vec::new(),
+                current: None,
+            };
+        }
+        let SlicerState::Stroke { targets, current } = &mut *slicer_state else {
+            return;
+        };
+        for (entity, pos) in blade_hits {
+            if hit.is_some_and(|(hit_entity, _)| hit_entity == entity) {
+                continue;
             }
-            SlicerState::FirstHit(pos, entity) => {
-                if hits.is_empty() {
-                    *slicer_state = SlicerState::NoTarget;
-                } else {
-                    let hit = &hits[0];
-                    if hit.0 == entity && hit.1.position() != pos {
-                        *slicer_state = SlicerState::Slicing {
-                            start: (pos, entity),
-                            last_hit: hit.1.position(),
-                        };
-                    } else if hit.0 != entity {
-                        let sliceable = sliceables_query.get(hit.0);
-                        if sliceable.is_ok() {
-                            *slicer_state = SlicerState::FirstHit(hit.1.position(), hit.0);
-                        }
-                    }
+            match targets.iter_mut().find(|target| target.entity == entity) {
+                Some(target) => {
+                    target.travelled += (pos - target.exit).length();
+                    target.exit = pos;
                 }
+                None => targets.push(StrokeTarget {
+                    entity,
+                    entry: pos,
+                    exit: pos,
+                    travelled: 0.,
+                }),
             }
-            SlicerState::Slicing { start, last_hit: _ } => {
-                if !hits.is_empty() {
-                    let hit = &hits[0];
-                    if hit.0 == start.1 && hit.1.position() != start.0 {
-                        *slicer_state = SlicerState::Slicing {
-                            start,
-                            last_hit: hit.1.position(),
-                        };
-                    } else if hit.0 != start.1 {
-                        let sliceable = sliceables_query.get(hit.0);
-                        if sliceable.is_ok() {
-                            *slicer_state = SlicerState::FirstHit(hit.1.position(), hit.0);
-                        }
-                    }
+        }
+        let Some((entity, pos)) = hit else {
+            *current = None;
+            return;
+        };
+        match targets.iter().position(|target| target.entity == entity) {
+            Some(index) => {
+                let target = &mut targets[index];
+                // Only count continuous movements on the target
+                if *current == Some(index) {
+                    target.travelled += (pos - target.exit).length();
                 }
+                target.exit = pos;
+                *current = Some(index);
             }
-        };
+            None => {
+                targets.push(StrokeTarget {
+                    entity,
+                    entry: pos,
+                    exit: pos,
+                    travelled: 0.,
+                });
+                *current = Some(targets.len() - 1);
+            }
+        }
     } else {
-        match *slicer_state {
-            SlicerState::Slicing { start, last_hit } => {
-                let slice_event = SliceAttemptEvent {
-                    begin: start.0,
-                    end: last_hit,
-                    entity: start.1,
-                };
-                commands.trigger(slice_event);
+        if let SlicerState::Stroke { targets, .. } = &*slicer_state {
+            if let (Some(first), Some(last)) = (targets.first(), targets.last()) {
+                let stroke = (first.entry, last.exit);
+                for target in targets.iter().filter(|target| target.entry != target.exit) {
+                    commands.trigger(SliceAttemptEvent {
+                        begin: target.entry,
+                        end: target.exit,
+                        stroke,
+                        entity: target.entity,
+                        quality: target.quality(),
+                    });
+                }
+                commands.trigger(SliceStrokeEnded);
             }
-            _ => (),
         }
         *slicer_state = SlicerState::Idle;
     }
 }
 
+/// Drops the current stroke without slicing anything
+fn cancel_stroke(mut slicer_state: ResMut<SlicerState>) {
+    if !matches!(*slicer_state, SlicerState::Idle) {
+        *slicer_state = SlicerState::Idle;
+    }
+}
+
 fn slice(
     trigger: Trigger<SliceAttemptEvent>,
     mut commands: Commands,
     meshes_assets: Res<Assets<Mesh>>,
     mut fragmentation_queue: ResMut<FragmentationQueue>,
+    mut stroke_results: ResMut<StrokeResults>,
     cameras: Query<&mut Transform, With<Camera>>,
+    mut armors: Query<&mut SliceArmor>,
     sliceables: Query<
-        (&Transform, &GlobalTransform, &Handle<Mesh>),
+        (
+            &Transform,
+            &GlobalTransform,
+            &Handle<Mesh>,
+            &Handle<StandardMaterial>,
+            Option<&SliceDepth>,
+        ),
         (With<Sliceable>, Without<Camera>),
     >,
 ) {
     let camera_tranform = cameras.single();
     let slice = trigger.event();
-    if let Ok((transform, global_transform, mesh_handle)) = sliceables.get(slice.entity) {
+    if let Ok((transform, global_transform, mesh_handle, material_handle, slice_depth)) =
+        sliceables.get(slice.entity)
+    {
         let mesh = meshes_assets.get(mesh_handle).unwrap();
 
         let inver_trsfrm = global_transform.affine().inverse();
-        let local_cam = inver_trsfrm.matrix3 * Vec3A::from(camera_tranform.translation)
-            + inver_trsfrm.translation;
-        let local_begin =
-            inver_trsfrm.matrix3 * Vec3A::from(slice.begin) + inver_trsfrm.translation;
-        let local_end = inver_trsfrm.matrix3 * Vec3A::from(slice.end) + inver_trsfrm.translation;
-        let local_qr = local_begin - local_cam;
-        let local_qs = local_end - local_cam;
+        let to_local =
+            |point: Vec3| inver_trsfrm.matrix3 * Vec3A::from(point) + inver_trsfrm.translation;
+        let local_cam = to_local(camera_tranform.translation);
+
+        // All the targets of a stroke are cut by the same plane, going through the camera and the stroke ends
+        let local_stroke_begin = to_local(slice.stroke.0);
+        let local_stroke_end = to_local(slice.stroke.1);
+        let stroke_normal = (local_stroke_begin - local_cam).cross(local_stroke_end - local_cam);
+        let (plane_origin, local_normal) = if stroke_normal.length_squared() > f32::EPSILON {
+            (local_stroke_begin, stroke_normal)
+        } else {
+            // Degenerate stroke, fallback on the target's own segment
+            let local_begin = to_local(slice.begin);
+            let local_end = to_local(slice.end);
+            (
+                local_begin,
+                (local_begin - local_cam).cross(local_end - local_cam),
+            )
+        };
 
-        let plane = Plane::new(local_begin, (local_qr.cross(local_qs).normalize()).into());
+        let plane = Plane::new(plane_origin, (local_normal.normalize()).into());
 
         if let Some(mesh_fragments) = slice_bevy_mesh(plane, mesh) {
+            if let Ok(mut armor) = armors.get_mut(slice.entity) {
+                armor.remaining = armor.remaining.saturating_sub(1);
+                if armor.remaining == 0 {
+                    commands.entity(slice.entity).remove::<SliceArmor>();
+                }
+                commands.trigger(SliceArmorHit {
+                    entity: slice.entity,
+                    pos: transform.translation,
+                });
+                return;
+            }
             // commands.spawn((
             //     PbrBundle {
             //         mesh: meshes_assets.add(Plane3d::new(qr.cross(qs))),
@@ -246,10 +467,29 @@ fn slice(
             //     },
             //     SliceableObject,
             // ));
+            if stroke_results.sliced_positions.is_empty() {
+                fragmentation_queue.swing += 1;
+                commands.trigger(SlashStarted {
+                    swing: fragmentation_queue.swing,
+                });
+            }
+            stroke_results.sliced_positions.push(transform.translation);
             // Let other systems react to the slice event with a valid entity
             commands.trigger(SliceEvent {
                 entity: slice.entity,
                 pos: transform.translation,
+                quality: slice.quality,
+            });
+            let world_normal: Vec3 = global_transform
+                .affine()
+                .matrix3
+                .mul_vec3a(local_normal)
+                .normalize()
+                .into();
+            commands.trigger(SlicePlaneEvent {
+                begin: slice.begin,
+                end: slice.end,
+                normal: world_normal,
             });
             // Set it as non sliceable
             commands.entity(slice.entity).remove::<Sliceable>();
@@ -258,45 +498,93 @@ fn slice(
                 sliced_entity: slice.entity,
                 slice_positions: (slice.begin, slice.end),
                 sliced_object_transform: transform.clone(),
+                sliced_object_material: material_handle.clone(),
+                slice_depth: slice_depth.copied().unwrap_or_default(),
                 fragments_meshes: mesh_fragments,
             };
-            fragmentation_queue.queue.push((
-                fragments_spawn,
-                // TODO Hardcode wait duration is hacky. Find a better way
-                Timer::new(
-                    Duration::from_millis(PLAYER_SLICE_FRAGMENTATION_DELAY_MS),
+            let swing = fragmentation_queue.swing;
+            fragmentation_queue.queue.push(QueuedFragmentation {
+                fragmentation: fragments_spawn,
+                swing,
+                fallback_timer: Timer::new(
+                    Duration::from_millis(PLAYER_SLICE_FRAGMENTATION_FALLBACK_DELAY_MS),
                     TimerMode::Once,
                 ),
-            ));
+            });
         }
     }
 }
 
+fn end_stroke(
+    _trigger: Trigger<SliceStrokeEnded>,
+    mut commands: Commands,
+    mut stroke_results: ResMut<StrokeResults>,
+) {
+    let sliced_positions = std::mem::take(&mut stroke_results.sliced_positions);
+    if sliced_positions.len() > 1 {
+        commands.trigger(MultiSliceEvent {
+            count: sliced_positions.len() as u32,
+            pos: sliced_positions.iter().sum::<Vec3>() / sliced_positions.len() as f32,
+        });
+    }
+}
+
 fn dequeue_fragmentations(
     mut commands: Commands,
     time: Res<Time>,
     mut fragmentation_queue: ResMut<FragmentationQueue>,
 ) {
-    let mut ready_fragmentations = Vec::new();
-    for (index, fragmentation) in fragmentation_queue.queue.iter_mut().enumerate() {
-        fragmentation.1.tick(time.delta());
-        if fragmentation.1.finished() {
-            ready_fragmentations.push(index);
-        }
+    for queued in fragmentation_queue.queue.iter_mut() {
+        queued.fallback_timer.tick(time.delta());
+    }
+    let (ready_fragmentations, pending_fragmentations): (Vec<_>, Vec<_>) =
+        std::mem::take(&mut fragmentation_queue.queue)
+            .into_iter()
+            .partition(|queued| queued.fallback_timer.finished());
+    fragmentation_queue.queue = pending_fragmentations;
+    for queued in ready_fragmentations {
+        commands.trigger(queued.fragmentation);
+    }
+}
+
+/// Fragments the sliced entities when the player's blade reaches them
+fn fragment_on_blade_contact(
+    trigger: Trigger<AnimationMarkerReached>,
+    mut commands: Commands,
+    mut fragmentation_queue: ResMut<FragmentationQueue>,
+    parents: Query<&Parent>,
+    players: Query<(), With<Player>>,
+    slash_swings: Query<&SlashSwing>,
+) {
+    if trigger.event().marker != AnimationMarker::BladeContact {
+        return;
+    }
+    let is_player_animation = parents
+        .iter_ancestors(trigger.entity())
+        .any(|ancestor| players.contains(ancestor));
+    if !is_player_animation {
+        return;
     }
-    for i in ready_fragmentations {
-        let fragmentation = fragmentation_queue.queue.remove(i); // Cannot swap remove without updating the indexes
-        commands.trigger(fragmentation.0);
+    let Ok(slash_swing) = slash_swings.get(trigger.entity()) else {
+        return;
+    };
+    // Fragments queued by a later stroke wait for their own swing
+    let (ready_fragmentations, pending_fragmentations): (Vec<_>, Vec<_>) =
+        std::mem::take(&mut fragmentation_queue.queue)
+            .into_iter()
+            .partition(|queued| queued.swing <= slash_swing.0);
+    fragmentation_queue.queue = pending_fragmentations;
+    for queued in ready_fragmentations {
+        commands.trigger(queued.fragmentation);
     }
 }
 
 fn slice_entity(
     trigger: Trigger<SliceEntity>,
     mut commands: Commands,
+    mut debris_budget: ResMut<DebrisBudget>,
     mut _materials: ResMut<Assets<StandardMaterial>>,
     mut meshes_assets: ResMut<Assets<Mesh>>,
-    gltf_handles: Res<HandleMap<GltfKey>>,
-    assets_gltf: Res<Assets<Gltf>>,
 ) {
     let fragments_info = trigger.event();
 
@@ -305,15 +593,21 @@ fn slice_entity(
         .entity(fragments_info.sliced_entity)
         .despawn_recursive();
 
-    // Spawn the fragments
-    let gltf_handle = &gltf_handles[&GltfKey::Dummy];
-    let Some(gltf) = assets_gltf.get(gltf_handle) else {
-        return;
+    // Spawn the fragments, they shatter together after a delay
+    let fragments_parent = spawn_debris_group(
+        &mut commands,
+        &mut debris_budget,
+        "Fragments",
+        Duration::from_millis(SLICED_FRAGMENTS_SHATTER_DELAY_MS),
+        DebrisExpiry::Shatter,
+    );
+    let mat_handle = &fragments_info.sliced_object_material;
+    let fragments_depth = SliceDepth {
+        depth: fragments_info.slice_depth.depth + 1,
+        ..fragments_info.slice_depth
     };
-    // TODO Get the mat handle from the sliced entity
-    let mat_handle = &gltf.materials[0];
-
-    // TODO Query for material + transform from the entity. See shatter_entity
+    let scale = fragments_info.sliced_object_transform.scale;
+    let volume_scale = scale.x * scale.y * scale.z;
 
     for mesh in fragments_info.fragments_meshes.iter() {
         let Some(collider) = Collider::from_bevy_mesh(&mesh, &ComputedColliderShape::ConvexHull)
@@ -327,7 +621,6 @@ fn slice_entity(
         let frag_entity = commands
             .spawn((
                 Name::new("Fragment"),
-                StateScoped(Screen::Playing),
                 PbrBundle {
                     mesh: mesh_handle.clone(),
                     transform: Transform::from(fragments_info.sliced_object_transform),
@@ -335,19 +628,29 @@ fn slice_entity(
                     material: mat_handle.clone(),
                     ..default()
                 },
-                // TODO Could make it sliceable again
-                // Sliceable,
                 // Physics
                 RigidBody::Dynamic,
                 collider,
                 ActiveCollisionTypes::default(),
+                ActiveEvents::COLLISION_EVENTS,
                 Friction::coefficient(DUMMY_FRAGMENT_FRICTION),
                 Restitution::coefficient(0.05),
                 ColliderMassProperties::Density(2.0),
                 // Logic
-                SlicedFragment::new(),
+                SlicedFragment::default(),
+                DebrisPiece { owns_mesh: true },
+                // Shattered when the debris group expires
+                Breakable::damage_only(),
+                fragments_depth,
             ))
             .id();
+        commands.entity(fragments_parent).add_child(frag_entity);
+
+        if fragments_depth.depth < fragments_depth.max_depth
+            && mesh_volume(mesh) * volume_scale >= MIN_RESLICE_VOLUME
+        {
+            commands.entity(frag_entity).insert(Sliceable);
+        }
 
         let slice_center =
             (fragments_info.slice_positions.0 + fragments_info.slice_positions.1) / 2.;
@@ -374,18 +677,27 @@ fn slice_entity(
     }
 }
 
-pub fn shatter_fragments(
-    mut commands: Commands,
-    time: Res<Time>,
-    mut fragments_query: Query<(Entity, &mut SlicedFragment)>,
+/// Only the ground and the static colliders count as a landing, not the other fragments
+fn detect_fragments_landing(
+    mut collision_events: EventReader<CollisionEvent>,
+    mut fragments_query: Query<&mut SlicedFragment>,
+    bodies_query: Query<Option<&RigidBody>, Without<SlicedFragment>>,
 ) {
-    for (entity, mut frag) in fragments_query.iter_mut() {
-        frag.shatter_timer.tick(time.delta());
-        if frag.shatter_timer.finished() {
-            commands.trigger(ShatterEntity {
-                entity,
-                impulse: Vec3::ZERO,
-            });
+    for collision_event in collision_events.read() {
+        let CollisionEvent::Started(e1, e2, _flags) = collision_event else {
+            continue;
+        };
+        for (entity, other) in [(e1, e2), (e2, e1)] {
+            // Colliders without a rigid body are static
+            let other_is_static = bodies_query
+                .get(*other)
+                .is_ok_and(|body| matches!(body, None | Some(RigidBody::Fixed)));
+            if !other_is_static {
+                continue;
+            }
+            if let Ok(mut fragment) = fragments_query.get_mut(*entity) {
+                fragment.landed = true;
+            }
         }
     }
 }
@@ -0,0 +1,268 @@
+//! Visual feedback for the slicing strokes: a blade trail following the cursor and a flash along the slice plane.
+
+use std::{collections::VecDeque, time::Duration};
+
+use bevy::{
+    app::{App, Update},
+    asset::{Assets, Handle},
+    color::{Alpha, Color, ColorToComponents},
+    core::Name,
+    math::{Mat3, Quat, Vec3},
+    pbr::{PbrBundle, StandardMaterial},
+    prelude::{
+        default, in_state, AlphaMode, Camera, Commands, Component, DespawnRecursiveExt, Entity,
+        IntoSystemConfigs, Mesh, OnEnter, Query, Rectangle, Res, ResMut, StateScoped, Transform,
+        Trigger, Visibility, With, Without,
+    },
+    reflect::Reflect,
+    render::{
+        mesh::{Indices, PrimitiveTopology},
+        render_asset::RenderAssetUsages,
+        view::NoFrustumCulling,
+    },
+    time::{Time, Timer, TimerMode},
+};
+
+use crate::{
+    game::{
+        arena::ArenaMode,
+        score::{
+            ScoreActionType, SCORE_BILLBOARD_TEXT_COLOR_BAD, SCORE_BILLBOARD_TEXT_COLOR_GOOD,
+            SCORE_BILLBOARD_TEXT_COLOR_PERFECT,
+        },
+    },
+    AppSet,
+};
+
+use super::slicing::{SlicePlaneEvent, SlicerState};
+
+/// How long a point stays in the trail
+pub const SLICE_TRAIL_LIFETIME_MS: u64 = 220;
+pub const SLICE_TRAIL_HALF_WIDTH: f32 = 0.12;
+/// Pulls the trail towards the camera so that it is not hidden by the sliced object
+pub const SLICE_TRAIL_CAMERA_OFFSET: f32 = 0.4;
+
+pub const SLICE_FLASH_DURATION_MS: u64 = 180;
+/// Depth of the flash along the slice plane
+pub const SLICE_FLASH_DEPTH: f32 = 2.5;
+pub const SLICE_FLASH_COLOR: Color = Color::srgb(1., 1., 0.9);
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<SliceVfx>();
+    app.register_type::<SliceTrail>();
+    app.register_type::<SliceFlash>();
+
+    app.add_systems(OnEnter(ArenaMode::Sword), spawn_slice_trail);
+    app.add_systems(
+        Update,
+        (update_slice_trail, fade_slice_flashes)
+            .in_set(AppSet::Update)
+            .run_if(in_state(ArenaMode::Sword)),
+    );
+
+    app.observe(spawn_slice_flash);
+}
+
+#[derive(Debug, Clone, Copy, Reflect)]
+struct TrailPoint {
+    pos: Vec3,
+    age: f32,
+}
+
+/// Marks the slicing visual effects, which should be ignored by the slicing raycasts
+#[derive(Component, Debug, Default, Reflect)]
+pub struct SliceVfx;
+
+/// Ribbon mesh following the cursor while slicing
+#[derive(Component, Debug, Default, Reflect)]
+pub struct SliceTrail {
+    points: VecDeque<TrailPoint>,
+    color: Color,
+}
+
+/// Short-lived quad showing where an object was cut
+#[derive(Component, Debug, Default, Reflect)]
+pub struct SliceFlash {
+    timer: Timer,
+}
+
+fn spawn_slice_trail(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let initial_trail = SliceTrail {
+        points: [TrailPoint {
+            pos: Vec3::ZERO,
+            age: 0.,
+        }; 2]
+            .into(),
+        color: Color::WHITE,
+    };
+    commands.spawn((
+        Name::new("Slice trail"),
+        StateScoped(ArenaMode::Sword),
+        PbrBundle {
+            mesh: meshes.add(build_trail_mesh(&initial_trail, Vec3::Z, 1.)),
+            material: materials.add(StandardMaterial {
+                base_color: Color::WHITE,
+                unlit: true,
+                alpha_mode: AlphaMode::Blend,
+                double_sided: true,
+                cull_mode: None,
+                ..default()
+            }),
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        // The mesh changes every frame, its bounds can't be trusted
+        NoFrustumCulling,
+        SliceTrail::default(),
+        SliceVfx,
+    ));
+}
+
+fn update_slice_trail(
+    time: Res<Time>,
+    slicer_state: Res<SlicerState>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    cameras: Query<&Transform, With<Camera>>,
+    mut trails: Query<(&mut SliceTrail, &Handle<Mesh>, &mut Visibility), Without<Camera>>,
+) {
+    let Ok(camera_transform) = cameras.get_single() else {
+        return;
+    };
+    let Ok((mut trail, mesh_handle, mut visibility)) = trails.get_single_mut() else {
+        return;
+    };
+
+    let lifetime = Duration::from_millis(SLICE_TRAIL_LIFETIME_MS).as_secs_f32();
+    for point in trail.points.iter_mut() {
+        point.age += time.delta_seconds();
+    }
+    while trail
+        .points
+        .front()
+        .is_some_and(|point| point.age >= lifetime)
+    {
+        trail.points.pop_front();
+    }
+
+    if let Some(pos) = slicer_state.stroke_point() {
+        if !trail.points.back().is_some_and(|last| last.pos == pos) {
+            trail.points.push_back(TrailPoint { pos, age: 0. });
+        }
+        trail.color = match slicer_state.expected_quality() {
+            Some(ScoreActionType::Perfect) => SCORE_BILLBOARD_TEXT_COLOR_PERFECT,
+            Some(_) => SCORE_BILLBOARD_TEXT_COLOR_GOOD,
+            None => SCORE_BILLBOARD_TEXT_COLOR_BAD,
+        };
+    }
+
+    if trail.points.len() < 2 {
+        *visibility = Visibility::Hidden;
+        return;
+    }
+    *visibility = Visibility::Inherited;
+
+    let Some(mesh) = meshes.get_mut(mesh_handle) else {
+        return;
+    };
+    *mesh = build_trail_mesh(&trail, camera_transform.translation, lifetime);
+}
+
+fn build_trail_mesh(trail: &SliceTrail, camera_pos: Vec3, lifetime: f32) -> Mesh {
+    let points: Vec<TrailPoint> = trail.points.iter().copied().collect();
+    let mut positions = Vec::with_capacity(points.len() * 2);
+    let mut normals = Vec::with_capacity(points.len() * 2);
+    let mut colors = Vec::with_capacity(points.len() * 2);
+    let mut indices = Vec::with_capacity((points.len() - 1) * 6);
+
+    for (i, point) in points.iter().enumerate() {
+        let previous = points[i.saturating_sub(1)].pos;
+        let next = points[(i + 1).min(points.len() - 1)].pos;
+        let to_camera = (camera_pos - point.pos).normalize_or_zero();
+        let fade = 1. - (point.age / lifetime).clamp(0., 1.);
+        let side =
+            (next - previous).cross(to_camera).normalize_or_zero() * SLICE_TRAIL_HALF_WIDTH * fade;
+        let center = point.pos + to_camera * SLICE_TRAIL_CAMERA_OFFSET;
+        let color = trail.color.with_alpha(fade).to_linear().to_f32_array();
+
+        positions.push((center + side).to_array());
+        positions.push((center - side).to_array());
+        normals.extend([to_camera.to_array(); 2]);
+        colors.extend([color; 2]);
+        if i > 0 {
+            let first = (i as u32 - 1) * 2;
+            indices.extend([first, first + 1, first + 2, first + 1, first + 3, first + 2]);
+        }
+    }
+
+    Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::default(),
+    )
+    .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+    .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
+    .with_inserted_attribute(Mesh::ATTRIBUTE_COLOR, colors)
+    .with_inserted_indices(Indices::U32(indices))
+}
+
+fn spawn_slice_flash(
+    trigger: Trigger<SlicePlaneEvent>,
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let slice = trigger.event();
+    let stroke = slice.end - slice.begin;
+    let Some(x_axis) = stroke.try_normalize() else {
+        return;
+    };
+    let z_axis = slice.normal;
+    let y_axis = z_axis.cross(x_axis).normalize();
+
+    commands.spawn((
+        Name::new("Slice flash"),
+        StateScoped(ArenaMode::Sword),
+        PbrBundle {
+            mesh: meshes.add(Rectangle::new(stroke.length(), SLICE_FLASH_DEPTH)),
+            material: materials.add(StandardMaterial {
+                base_color: SLICE_FLASH_COLOR,
+                unlit: true,
+                alpha_mode: AlphaMode::Add,
+                double_sided: true,
+                cull_mode: None,
+                ..default()
+            }),
+            transform: Transform::from_translation((slice.begin + slice.end) / 2.)
+                .with_rotation(Quat::from_mat3(&Mat3::from_cols(x_axis, y_axis, z_axis))),
+            ..default()
+        },
+        SliceFlash {
+            timer: Timer::new(
+                Duration::from_millis(SLICE_FLASH_DURATION_MS),
+                TimerMode::Once,
+            ),
+        },
+        SliceVfx,
+    ));
+}
+
+fn fade_slice_flashes(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut flashes: Query<(Entity, &mut SliceFlash, &Handle<StandardMaterial>)>,
+) {
+    for (entity, mut flash, material_handle) in flashes.iter_mut() {
+        flash.timer.tick(time.delta());
+        if flash.timer.finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+        if let Some(material) = materials.get_mut(material_handle) {
+            material.base_color.set_alpha(1. - flash.timer.fraction());
+        }
+    }
+}
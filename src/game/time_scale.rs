@@ -0,0 +1,248 @@
+//! Slow-motion and hit-stop service, scaling the game time from a single place.
+//!
+//! The scale is applied to `Time<Virtual>`, which is the `Time` read by the gameplay timers, the Rapier timestep,
+//! the animation players and the tweening animators. The audio sinks are not driven by a clock, their speed (and pitch) is set here.
+
+use std::time::Duration;
+
+use bevy::{
+    app::{App, Update},
+    audio::{AudioSink, AudioSinkPlayback},
+    prelude::{
+        Commands, Event, IntoSystemConfigs, OnEnter, OnExit, Query, Res, ResMut, Resource, Trigger,
+    },
+    reflect::Reflect,
+    time::{Real, Time, Timer, TimerMode, Virtual},
+    utils::HashMap,
+};
+
+use crate::{screen::Screen, AppSet};
+
+use super::{
+    accessibility::AccessibilitySettings,
+    arena::ArenaMode,
+    score::{LastChance, ScoreAction, ScoreActionType},
+    shattering::ShatterEntity,
+    sword::slicing::SliceEvent,
+};
+
+/// Scale of the short slow-motion after a perfect action
+pub const BULLET_TIME_SCALE: f32 = 0.35;
+pub const BULLET_TIME_DURATION_MS: u64 = 450;
+/// Scale of the slow-motion when the score gets close to a game over
+pub const LAST_CHANCE_TIME_SCALE: f32 = 0.25;
+pub const LAST_CHANCE_DURATION_MS: u64 = 1200;
+/// How fast the applied scale moves towards the requested one, per real second
+pub const TIME_SCALE_CHANGE_PER_SEC: f32 = 6.;
+
+/// Longest hit-stop, in frames. Hit-stops do not add up, the longest one wins.
+pub const HIT_STOP_MAX_FRAMES: u32 = 6;
+/// Frozen frames per unit of impact speed when something shatters
+pub const HIT_STOP_FRAMES_PER_IMPACT_SPEED: f32 = 0.15;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<TimeScale>();
+    app.init_resource::<TimeScale>();
+
+    app.add_systems(OnEnter(ArenaMode::GameOver), reset_time_scale);
+    app.add_systems(OnExit(Screen::Playing), reset_time_scale);
+    app.add_systems(Update, update_time_scale.in_set(AppSet::TickTimers));
+
+    app.observe(bullet_time_on_perfect_action);
+    app.observe(bullet_time_on_last_chance);
+    app.observe(start_hit_stop);
+    app.observe(hit_stop_on_score_actions);
+    app.observe(hit_stop_on_slices);
+    app.observe(hit_stop_on_shatters);
+}
+
+/// Who asked for a time scale. Each source has at most one active request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum TimeScaleSource {
+    PowerUp,
+    BulletTime,
+    LastChance,
+}
+
+#[derive(Debug, Reflect)]
+struct TimeScaleRequest {
+    scale: f32,
+    /// Ticked with the real time. Lasts until released if not set.
+    duration: Option<Timer>,
+}
+
+/// Freezes the game time for a few frames
+#[derive(Event, Debug)]
+pub struct HitStop {
+    pub frames: u32,
+}
+
+/// The slowest requested scale wins
+#[derive(Resource, Debug, Reflect)]
+pub struct TimeScale {
+    requests: HashMap<TimeScaleSource, TimeScaleRequest>,
+    /// Scale currently applied, eased towards the requested one
+    current: f32,
+    /// Remaining frozen frames
+    hit_stop_frames: u32,
+}
+impl Default for TimeScale {
+    fn default() -> Self {
+        Self {
+            requests: HashMap::new(),
+            current: 1.,
+            hit_stop_frames: 0,
+        }
+    }
+}
+impl TimeScale {
+    /// Replaces the previous request of `source`
+    pub fn request(&mut self, source: TimeScaleSource, scale: f32, duration: Option<Duration>) {
+        self.requests.insert(
+            source,
+            TimeScaleRequest {
+                scale,
+                duration: duration.map(|duration| Timer::new(duration, TimerMode::Once)),
+            },
+        );
+    }
+
+    pub fn release(&mut self, source: TimeScaleSource) {
+        self.requests.remove(&source);
+    }
+
+    pub fn target(&self) -> f32 {
+        self.requests
+            .values()
+            .map(|request| request.scale)
+            .fold(1., f32::min)
+    }
+
+    pub fn current(&self) -> f32 {
+        self.current
+    }
+}
+
+fn update_time_scale(
+    real_time: Res<Time<Real>>,
+    mut time_scale: ResMut<TimeScale>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    audio_sinks: Query<&AudioSink>,
+) {
+    for request in time_scale.requests.values_mut() {
+        if let Some(timer) = request.duration.as_mut() {
+            timer.tick(real_time.delta());
+        }
+    }
+    time_scale.requests.retain(|_, request| {
+        request
+            .duration
+            .as_ref()
+            .map_or(true, |timer| !timer.finished())
+    });
+
+    let target = time_scale.target();
+    let max_change = TIME_SCALE_CHANGE_PER_SEC * real_time.delta_seconds();
+    time_scale.current += (target - time_scale.current).clamp(-max_change, max_change);
+
+    // The sounds keep playing during a hit-stop
+    let virtual_scale = if time_scale.hit_stop_frames > 0 {
+        time_scale.hit_stop_frames -= 1;
+        0.
+    } else {
+        time_scale.current
+    };
+    apply_time_scale(
+        virtual_scale,
+        time_scale.current,
+        &mut virtual_time,
+        &audio_sinks,
+    );
+}
+
+fn apply_time_scale(
+    virtual_scale: f32,
+    audio_scale: f32,
+    virtual_time: &mut Time<Virtual>,
+    audio_sinks: &Query<&AudioSink>,
+) {
+    if virtual_time.relative_speed() != virtual_scale {
+        virtual_time.set_relative_speed(virtual_scale);
+    }
+    // Also catches the sounds started since the last change
+    for sink in audio_sinks.iter() {
+        if sink.speed() != audio_scale {
+            sink.set_speed(audio_scale);
+        }
+    }
+}
+
+fn reset_time_scale(
+    mut time_scale: ResMut<TimeScale>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    audio_sinks: Query<&AudioSink>,
+) {
+    *time_scale = TimeScale::default();
+    apply_time_scale(1., 1., &mut virtual_time, &audio_sinks);
+}
+
+fn bullet_time_on_perfect_action(trigger: Trigger<ScoreAction>, mut time_scale: ResMut<TimeScale>) {
+    if trigger.event().action != ScoreActionType::Perfect {
+        return;
+    }
+    time_scale.request(
+        TimeScaleSource::BulletTime,
+        BULLET_TIME_SCALE,
+        Some(Duration::from_millis(BULLET_TIME_DURATION_MS)),
+    );
+}
+
+fn bullet_time_on_last_chance(_trigger: Trigger<LastChance>, mut time_scale: ResMut<TimeScale>) {
+    time_scale.request(
+        TimeScaleSource::LastChance,
+        LAST_CHANCE_TIME_SCALE,
+        Some(Duration::from_millis(LAST_CHANCE_DURATION_MS)),
+    );
+}
+
+fn start_hit_stop(
+    trigger: Trigger<HitStop>,
+    accessibility: Res<AccessibilitySettings>,
+    mut time_scale: ResMut<TimeScale>,
+) {
+    if !accessibility.hit_stop {
+        return;
+    }
+    let frames = trigger.event().frames.min(HIT_STOP_MAX_FRAMES);
+    time_scale.hit_stop_frames = time_scale.hit_stop_frames.max(frames);
+}
+
+/// Better actions hit harder
+fn hit_stop_frames(action: ScoreActionType) -> u32 {
+    match action {
+        ScoreActionType::Bad => 0,
+        ScoreActionType::Good => 2,
+        ScoreActionType::Prop => 3,
+        ScoreActionType::Perfect | ScoreActionType::Juggle => 4,
+        ScoreActionType::MultiSlice(count) => 2 + count,
+    }
+}
+
+fn hit_stop_on_score_actions(trigger: Trigger<ScoreAction>, mut commands: Commands) {
+    commands.trigger(HitStop {
+        frames: hit_stop_frames(trigger.event().action),
+    });
+}
+
+fn hit_stop_on_slices(trigger: Trigger<SliceEvent>, mut commands: Commands) {
+    commands.trigger(HitStop {
+        frames: hit_stop_frames(trigger.event().quality),
+    });
+}
+
+fn hit_stop_on_shatters(trigger: Trigger<ShatterEntity>, mut commands: Commands) {
+    let frames = trigger.event().impact_velocity.length() * HIT_STOP_FRAMES_PER_IMPACT_SPEED;
+    commands.trigger(HitStop {
+        frames: frames as u32,
+    });
+}
@@ -0,0 +1,304 @@
+//! Scripted first game, walking the player through each mode.
+//!
+//! The tutorial drives the weapon cycle itself instead of picking random modes, and a score below zero does not end it.
+
+use std::time::Duration;
+
+use bevy::{
+    app::{App, Update},
+    color::{palettes::css::LIME, Color},
+    math::Vec3,
+    prelude::{
+        in_state, resource_exists, BuildChildren, Camera, Commands, Component, Event, Gizmos,
+        IntoSystemConfigs, NextState, OnEnter, OnExit, Query, Res, ResMut, Resource, StateScoped,
+        Transform, Trigger, With, Without,
+    },
+    reflect::Reflect,
+    text::Text,
+    time::{Real, Time, Timer, TimerMode},
+    ui::Interaction,
+};
+
+use crate::{screen::Screen, ui::prelude::*, AppSet};
+
+use super::{
+    arena::ArenaMode,
+    assets::{FontKey, HandleMap, DEFAULT_FONT_KEY},
+    cycle::Cycle,
+    score::{ScoreAction, ScoreActionType},
+    spawn::dummy::Dummy,
+    sword::slicing::SliceEvent,
+};
+
+/// Time left before the scripted weapon change of the cycle step
+pub const TUTORIAL_CYCLE_STEP_DURATION_MS: u64 = 8000;
+/// The tutorial returns to the main menu after this delay once done
+pub const TUTORIAL_END_DELAY_MS: u64 = 4000;
+/// Height of the slice hint above the dummy's feet
+pub const TUTORIAL_SLICE_HINT_HEIGHT: f32 = 2.;
+pub const TUTORIAL_SLICE_HINT_HALF_LENGTH: f32 = 1.5;
+pub const TUTORIAL_SLICE_HINT_COLOR: Color = Color::Srgba(LIME);
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Tutorial>();
+    app.register_type::<TutorialStep>();
+
+    app.add_systems(
+        OnEnter(Screen::Playing),
+        setup_tutorial_ui.run_if(resource_exists::<Tutorial>),
+    );
+    app.add_systems(OnExit(Screen::Playing), end_tutorial);
+    app.add_systems(
+        Update,
+        (
+            handle_tutorial_action.in_set(AppSet::RecordInput),
+            (wait_for_weapon_change, finish_tutorial, draw_slice_hint).in_set(AppSet::Update),
+        )
+            .run_if(in_state(Screen::Playing).and_then(resource_exists::<Tutorial>)),
+    );
+
+    app.observe(advance_on_slice);
+    app.observe(advance_on_score_action);
+    app.observe(advance_tutorial);
+}
+
+/// Present while the tutorial runs, insert it before entering [`Screen::Playing`]
+#[derive(Resource, Debug, Reflect)]
+pub struct Tutorial {
+    step: TutorialStep,
+    end_timer: Timer,
+}
+impl Default for Tutorial {
+    fn default() -> Self {
+        Self {
+            step: TutorialStep::Slice,
+            end_timer: Timer::new(
+                Duration::from_millis(TUTORIAL_END_DELAY_MS),
+                TimerMode::Once,
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum TutorialStep {
+    /// Waits for a dummy to be sliced
+    Slice,
+    /// Waits for a missed dummy or a sliced decoy
+    SliceMiss,
+    /// Waits for a blocked jug
+    Block,
+    /// Waits for the cycle to bring the sword back
+    WeaponCycle,
+    Done,
+}
+impl TutorialStep {
+    pub fn next(&self) -> Self {
+        match self {
+            TutorialStep::Slice => TutorialStep::SliceMiss,
+            TutorialStep::SliceMiss => TutorialStep::Block,
+            TutorialStep::Block => TutorialStep::WeaponCycle,
+            TutorialStep::WeaponCycle | TutorialStep::Done => TutorialStep::Done,
+        }
+    }
+
+    pub fn instructions(&self) -> &'static str {
+        match self {
+            TutorialStep::Slice => {
+                "Hold the left mouse button and trace a line through a dummy to slice it"
+            }
+            TutorialStep::SliceMiss => {
+                "Dummies left standing cost points, and so do the red decoys. Let one go, or slice a decoy"
+            }
+            TutorialStep::Block => {
+                "Shield in hand! Turn with the cursor to face the jugs and block one"
+            }
+            TutorialStep::WeaponCycle => {
+                "The bottom left icon shows the next weapon and when it comes. Hold on until the switch"
+            }
+            TutorialStep::Done => "Below zero points, the game is over. Good luck, gladiator!",
+        }
+    }
+}
+
+/// Triggered to move on to the next [`TutorialStep`]
+#[derive(Event, Debug)]
+pub struct AdvanceTutorial;
+
+#[derive(Component)]
+pub struct TutorialText;
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+enum TutorialAction {
+    Skip,
+}
+
+/// Starts the scripted cycle, in place of the random first mode
+pub fn scripted_cycle() -> Cycle {
+    let mut next_mode_timer = Timer::new(
+        Duration::from_millis(TUTORIAL_CYCLE_STEP_DURATION_MS),
+        TimerMode::Repeating,
+    );
+    next_mode_timer.pause();
+    Cycle {
+        current_mode: ArenaMode::Sword,
+        next_mode: ArenaMode::Shield,
+        next_mode_timer,
+        completed_cycles: 0,
+    }
+}
+
+fn setup_tutorial_ui(
+    mut commands: Commands,
+    tutorial: Res<Tutorial>,
+    font_handles: Res<HandleMap<FontKey>>,
+) {
+    let font = font_handles.get(&DEFAULT_FONT_KEY).unwrap().clone();
+    commands
+        .overlay_ui_root()
+        .insert(StateScoped(Screen::Playing))
+        .with_children(|children| {
+            children.dynamic_label_with_marker(
+                "",
+                tutorial.step.instructions(),
+                TutorialText,
+                font.clone_weak(),
+            );
+            children
+                .button("Skip", font.clone_weak())
+                .insert(TutorialAction::Skip);
+        });
+}
+
+fn end_tutorial(mut commands: Commands) {
+    commands.remove_resource::<Tutorial>();
+}
+
+fn handle_tutorial_action(
+    mut next_screen: ResMut<NextState<Screen>>,
+    mut button_query: InteractionQuery<&TutorialAction>,
+) {
+    for (interaction, action) in &mut button_query {
+        if matches!(interaction, Interaction::Pressed) {
+            match action {
+                TutorialAction::Skip => next_screen.set(Screen::MainMenu),
+            }
+        }
+    }
+}
+
+fn advance_on_slice(
+    trigger: Trigger<SliceEvent>,
+    mut commands: Commands,
+    tutorial: Option<Res<Tutorial>>,
+    dummies_query: Query<&Dummy>,
+) {
+    let Some(tutorial) = tutorial else {
+        return;
+    };
+    let Ok(dummy) = dummies_query.get(trigger.event().entity) else {
+        return;
+    };
+    if tutorial.step == TutorialStep::Slice && !dummy.kind.archetype().is_decoy {
+        commands.trigger(AdvanceTutorial);
+    }
+}
+
+fn advance_on_score_action(
+    trigger: Trigger<ScoreAction>,
+    mut commands: Commands,
+    tutorial: Option<Res<Tutorial>>,
+    cycle: Res<Cycle>,
+) {
+    let Some(tutorial) = tutorial else {
+        return;
+    };
+    let advance = match (tutorial.step, trigger.event().action) {
+        (TutorialStep::SliceMiss, ScoreActionType::Bad) => cycle.current_mode == ArenaMode::Sword,
+        (TutorialStep::Block, ScoreActionType::Good) => cycle.current_mode == ArenaMode::Shield,
+        _ => false,
+    };
+    if advance {
+        commands.trigger(AdvanceTutorial);
+    }
+}
+
+fn advance_tutorial(
+    _trigger: Trigger<AdvanceTutorial>,
+    tutorial: Option<ResMut<Tutorial>>,
+    mut cycle: ResMut<Cycle>,
+    mut next_arena_mode: ResMut<NextState<ArenaMode>>,
+    mut text_query: Query<&mut Text, With<TutorialText>>,
+) {
+    let Some(mut tutorial) = tutorial else {
+        return;
+    };
+    tutorial.step = tutorial.step.next();
+    match tutorial.step {
+        TutorialStep::Block => {
+            next_arena_mode.set(ArenaMode::Shield);
+            cycle.current_mode = ArenaMode::Shield;
+            cycle.next_mode = ArenaMode::Sword;
+        }
+        TutorialStep::WeaponCycle => {
+            cycle.next_mode_timer.reset();
+            cycle.next_mode_timer.unpause();
+        }
+        TutorialStep::Done => {
+            cycle.next_mode_timer.pause();
+            tutorial.end_timer.reset();
+        }
+        TutorialStep::Slice | TutorialStep::SliceMiss => (),
+    }
+
+    for mut text in text_query.iter_mut() {
+        text.sections[1].value = tutorial.step.instructions().to_string();
+    }
+}
+
+fn wait_for_weapon_change(mut commands: Commands, tutorial: Res<Tutorial>, cycle: Res<Cycle>) {
+    if tutorial.step == TutorialStep::WeaponCycle && cycle.current_mode == ArenaMode::Sword {
+        commands.trigger(AdvanceTutorial);
+    }
+}
+
+fn finish_tutorial(
+    real_time: Res<Time<Real>>,
+    mut tutorial: ResMut<Tutorial>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    if tutorial.step != TutorialStep::Done {
+        return;
+    }
+    tutorial.end_timer.tick(real_time.delta());
+    if tutorial.end_timer.just_finished() {
+        next_screen.set(Screen::MainMenu);
+    }
+}
+
+/// Shows a stroke across the dummies while the slice is taught
+fn draw_slice_hint(
+    mut gizmos: Gizmos,
+    tutorial: Res<Tutorial>,
+    camera_query: Query<&Transform, With<Camera>>,
+    dummies_query: Query<(&Transform, &Dummy), Without<Camera>>,
+) {
+    if tutorial.step != TutorialStep::Slice {
+        return;
+    }
+    let Ok(camera_transform) = camera_query.get_single() else {
+        return;
+    };
+    let right = *camera_transform.right();
+    for (transform, dummy) in dummies_query.iter() {
+        if dummy.kind.archetype().is_decoy {
+            continue;
+        }
+        let center = transform.translation + TUTORIAL_SLICE_HINT_HEIGHT * Vec3::Y;
+        gizmos.arrow(
+            center - TUTORIAL_SLICE_HINT_HALF_LENGTH * right,
+            center + TUTORIAL_SLICE_HINT_HALF_LENGTH * right,
+            TUTORIAL_SLICE_HINT_COLOR,
+        );
+    }
+}
@@ -101,6 +101,8 @@ fn spawn_camera(mut commands: Commands) {
         // [ui node outlines](https://bevyengine.org/news/bevy-0-14/#ui-node-outline-gizmos)
         // for debugging. So it's good to have this here for future-proofing.
         IsDefaultUiCamera,
+        // Spatial sounds are panned relatively to the camera
+        SpatialListener::default(),
         PanOrbitCameraBundle {
             camera: Camera3dBundle {
                 transform: Transform::from_translation(camera_position)
@@ -121,6 +123,8 @@ fn spawn_camera(mut commands: Commands) {
                 zoom_key: None,
                 scroll_action: None,
                 auto_orbit: false,
+                // Keeps the arena walls out of the view when orbiting
+                avoid_collisions: true,
                 ..Default::default()
             },
         },
@@ -7,8 +7,8 @@ use super::Screen;
 use crate::{
     game::{
         assets::{
-            all_assets_loaded, all_assets_processed, process_dummy_asset, process_jug_asset,
-            process_shield_asset, AssetsProcessing,
+            all_assets_loaded, all_assets_processed, process_dummy_asset,
+            process_fracture_libraries, process_jug_asset, process_shield_asset, AssetsProcessing,
         },
         spawn::arena::SpawnArena,
     },
@@ -21,7 +21,12 @@ pub(super) fn plugin(app: &mut App) {
     app.add_systems(
         Update,
         (
-            (process_jug_asset, process_dummy_asset, process_shield_asset)
+            (
+                process_jug_asset,
+                process_dummy_asset,
+                process_shield_asset,
+                process_fracture_libraries,
+            )
                 .run_if(in_state(Screen::Loading)),
             continue_to_title.run_if(
                 in_state(Screen::Loading)
@@ -5,8 +5,12 @@ use bevy::prelude::*;
 use super::Screen;
 use crate::{
     game::{
+        accessibility::AccessibilitySettings,
+        arena::ArenaMode,
         assets::{FontKey, HandleMap, DEFAULT_FONT_KEY},
-        camera::{PanOrbitSettings, PanOrbitState},
+        camera_director::{CameraPresetKey, SetCameraPreset},
+        practice::Practice,
+        tutorial::Tutorial,
     },
     ui::prelude::*,
 };
@@ -25,6 +29,11 @@ pub(super) fn plugin(app: &mut App) {
 #[reflect(Component)]
 enum MainMenuAction {
     Play,
+    Tutorial,
+    Practice(ArenaMode),
+    ToggleScreenShake,
+    ToggleHitStop,
+    ToggleThreatPing,
     // Credits,
     /// Exit doesn't work well with embedded applications.
     #[cfg(not(target_family = "wasm"))]
@@ -35,7 +44,7 @@ fn enter_title(
     mut commands: Commands,
     // mut fonts: ResMut<Assets<Font>>,
     font_handles: Res<HandleMap<FontKey>>,
-    mut camera_query: Query<(&mut PanOrbitState, &mut PanOrbitSettings)>,
+    accessibility: Res<AccessibilitySettings>,
 ) {
     let font = font_handles.get(&DEFAULT_FONT_KEY).unwrap().clone();
     // Seems to need additonal setings. Spacing is not right
@@ -49,34 +58,88 @@ fn enter_title(
         .bottom_ui_root()
         .insert(StateScoped(Screen::MainMenu))
         .with_children(|children| {
-            children.button("Play", font).insert(MainMenuAction::Play);
+            children
+                .button("Play", font.clone_weak())
+                .insert(MainMenuAction::Play);
+            children
+                .button("Tutorial", font.clone_weak())
+                .insert(MainMenuAction::Tutorial);
+            children
+                .button("Practice Sword", font.clone_weak())
+                .insert(MainMenuAction::Practice(ArenaMode::Sword));
+            children
+                .button("Practice Shield", font.clone_weak())
+                .insert(MainMenuAction::Practice(ArenaMode::Shield));
+            children
+                .button(
+                    toggle_text("Shake", accessibility.screen_shake),
+                    font.clone_weak(),
+                )
+                .insert(MainMenuAction::ToggleScreenShake);
+            children
+                .button(
+                    toggle_text("Hit-stop", accessibility.hit_stop),
+                    font.clone_weak(),
+                )
+                .insert(MainMenuAction::ToggleHitStop);
+            children
+                .button(
+                    toggle_text("Ping", accessibility.threat_ping),
+                    font.clone_weak(),
+                )
+                .insert(MainMenuAction::ToggleThreatPing);
             // children.button("Credits").insert(TitleAction::Credits);
 
             // #[cfg(not(target_family = "wasm"))]
             // children.button("Exit").insert(TitleAction::Exit);
         });
 
-    // Setup camera
-    let Ok((mut cam_state, mut cam_settings)) = camera_query.get_single_mut() else {
-        return;
-    };
-    cam_state.center = Vec3::ZERO;
-    cam_state.radius = 51.71328;
-    cam_state.pitch = -0.3002041;
-    cam_state.yaw = 0.5580911;
+    commands.trigger(SetCameraPreset(CameraPresetKey::Title));
+}
 
-    cam_settings.auto_orbit = true;
+fn toggle_text(setting: &str, enabled: bool) -> String {
+    format!("{}: {}", setting, if enabled { "On" } else { "Off" })
 }
 
 fn handle_title_action(
+    mut commands: Commands,
     mut next_screen: ResMut<NextState<Screen>>,
-    mut button_query: InteractionQuery<&MainMenuAction>,
+    mut accessibility: ResMut<AccessibilitySettings>,
+    mut button_query: InteractionQuery<(&MainMenuAction, &Children)>,
+    mut texts_query: Query<&mut Text>,
     #[cfg(not(target_family = "wasm"))] mut app_exit: EventWriter<AppExit>,
 ) {
-    for (interaction, action) in &mut button_query {
+    for (interaction, (action, children)) in &mut button_query {
         if matches!(interaction, Interaction::Pressed) {
+            // Toggles update the text of their button
+            let mut set_button_text = |text: String| {
+                let mut texts = texts_query.iter_many_mut(children.iter());
+                while let Some(mut button_text) = texts.fetch_next() {
+                    button_text.sections[0].value = text.clone();
+                }
+            };
             match action {
-                MainMenuAction::Play => next_screen.set(Screen::Playing),
+                MainMenuAction::Play => next_screen.set(Screen::ModeSelect),
+                MainMenuAction::Tutorial => {
+                    commands.insert_resource(Tutorial::default());
+                    next_screen.set(Screen::Playing);
+                }
+                MainMenuAction::Practice(mode) => {
+                    commands.insert_resource(Practice::new(*mode));
+                    next_screen.set(Screen::Playing);
+                }
+                MainMenuAction::ToggleScreenShake => {
+                    accessibility.screen_shake = !accessibility.screen_shake;
+                    set_button_text(toggle_text("Shake", accessibility.screen_shake));
+                }
+                MainMenuAction::ToggleHitStop => {
+                    accessibility.hit_stop = !accessibility.hit_stop;
+                    set_button_text(toggle_text("Hit-stop", accessibility.hit_stop));
+                }
+                MainMenuAction::ToggleThreatPing => {
+                    accessibility.threat_ping = !accessibility.threat_ping;
+                    set_button_text(toggle_text("Ping", accessibility.threat_ping));
+                }
                 // TitleAction::Credits => next_screen.set(Screen::Credits),
                 #[cfg(not(target_family = "wasm"))]
                 MainMenuAction::Exit => {
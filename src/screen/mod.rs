@@ -1,5 +1,6 @@
 mod loading;
 mod main_menu;
+mod mode_select;
 mod playing;
 
 use bevy::prelude::*;
@@ -11,7 +12,12 @@ pub(super) fn plugin(app: &mut App) {
     app.enable_state_scoped_entities::<Screen>();
     app.enable_state_scoped_entities::<ArenaMode>();
 
-    app.add_plugins((loading::plugin, main_menu::plugin, playing::plugin));
+    app.add_plugins((
+        loading::plugin,
+        main_menu::plugin,
+        mode_select::plugin,
+        playing::plugin,
+    ));
 }
 
 /// The game's main screen states.
@@ -20,5 +26,6 @@ pub enum Screen {
     #[default]
     Loading,
     MainMenu,
+    ModeSelect,
     Playing,
 }
@@ -0,0 +1,87 @@
+//! The screen to pick the rules of the next run.
+
+use bevy::{input::common_conditions::input_just_pressed, prelude::*};
+
+use super::Screen;
+use crate::{
+    game::{
+        assets::{FontKey, HandleMap, DEFAULT_FONT_KEY},
+        game_mode::{GameMode, GameModeKind, Leaderboards},
+    },
+    ui::prelude::*,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Screen::ModeSelect), enter_mode_select);
+
+    app.register_type::<ModeSelectAction>();
+    app.add_systems(
+        Update,
+        (
+            handle_mode_select_action,
+            return_to_title_screen.run_if(input_just_pressed(KeyCode::Escape)),
+        )
+            .run_if(in_state(Screen::ModeSelect)),
+    );
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+enum ModeSelectAction {
+    Start(GameModeKind),
+    Back,
+}
+
+fn best_text(leaderboards: &Leaderboards, kind: GameModeKind) -> String {
+    let scores = leaderboards.scores(kind);
+    if scores.is_empty() {
+        return "Best: -".to_string();
+    }
+    let scores: Vec<String> = scores.iter().map(|score| score.to_string()).collect();
+    format!("Best: {}", scores.join(" / "))
+}
+
+fn enter_mode_select(
+    mut commands: Commands,
+    font_handles: Res<HandleMap<FontKey>>,
+    leaderboards: Res<Leaderboards>,
+) {
+    let font = font_handles.get(&DEFAULT_FONT_KEY).unwrap().clone();
+    commands
+        .ui_root()
+        .insert(StateScoped(Screen::ModeSelect))
+        .with_children(|children| {
+            children.header("Game mode");
+            for kind in GameModeKind::ALL {
+                children
+                    .button(kind.name(), font.clone_weak())
+                    .insert(ModeSelectAction::Start(kind));
+                children.label(best_text(&leaderboards, kind));
+            }
+            children
+                .button("Back", font.clone_weak())
+                .insert(ModeSelectAction::Back);
+        });
+}
+
+fn handle_mode_select_action(
+    mut commands: Commands,
+    mut next_screen: ResMut<NextState<Screen>>,
+    mut button_query: InteractionQuery<&ModeSelectAction>,
+) {
+    for (interaction, action) in &mut button_query {
+        if matches!(interaction, Interaction::Pressed) {
+            match action {
+                ModeSelectAction::Start(kind) => {
+                    commands.insert_resource(GameMode::new(*kind));
+                    next_screen.set(Screen::Playing);
+                }
+                ModeSelectAction::Back => next_screen.set(Screen::MainMenu),
+            }
+        }
+    }
+}
+
+fn return_to_title_screen(mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::MainMenu);
+}
@@ -2,6 +2,8 @@ use bevy::prelude::*;
 
 use crate::game::assets::SfxKey;
 
+use super::slider::Slider;
+
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<InteractionPalette>();
     app.add_systems(Update, (apply_interaction_palette, trigger_interaction_sfx));
@@ -19,6 +21,15 @@ pub struct InteractionPalette {
     pub pressed: Color,
 }
 
+/// Run condition, true while the cursor hovers or presses a button or a slider
+pub fn cursor_over_widget(
+    interactions: Query<&Interaction, Or<(With<Button>, With<Slider>)>>,
+) -> bool {
+    interactions
+        .iter()
+        .any(|interaction| *interaction != Interaction::None)
+}
+
 fn apply_interaction_palette(
     mut palette_query: InteractionQuery<(&InteractionPalette, &mut BackgroundColor)>,
 ) {
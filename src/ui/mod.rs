@@ -5,12 +5,14 @@
 
 pub mod interaction;
 pub mod palette;
+pub mod slider;
 pub mod widgets;
 
 pub mod prelude {
     pub use super::{
         interaction::{InteractionPalette, InteractionQuery},
         palette as ui_palette,
+        slider::Slider,
         widgets::{Containers as _, Widgets as _},
     };
 }
@@ -18,5 +20,5 @@ pub mod prelude {
 use bevy::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_plugins(interaction::plugin);
+    app.add_plugins((interaction::plugin, slider::plugin));
 }
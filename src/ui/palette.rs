@@ -9,3 +9,4 @@ pub const HEADER_TEXT_COLOR: Color = Color::srgb(0.867, 0.827, 0.412);
 pub const NODE_BACKGROUND_COLOR: Color = Color::srgb(1., 0.572, 0.282);
 pub const BUTTON_HOVERED_BACKGROUND: Color = Color::srgb(1., 0.404, 0.);
 pub const BUTTON_PRESSED_BACKGROUND: Color = Color::srgb(1., 0.404, 0.);
+pub const SLIDER_TRACK_COLOR: Color = Color::srgba(0.1, 0.1, 0.1, 0.6);
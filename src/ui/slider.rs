@@ -0,0 +1,46 @@
+//! Horizontal slider, dragged with the mouse.
+
+use bevy::{prelude::*, ui::RelativeCursorPosition};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Slider>();
+    app.add_systems(Update, (drag_sliders, update_slider_fills).chain());
+}
+
+/// Put on the slider track, `value` goes from 0 at the left to 1 at the right
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct Slider {
+    pub value: f32,
+}
+
+/// Filled part of a slider track
+#[derive(Component, Debug)]
+pub struct SliderFill;
+
+fn drag_sliders(mut sliders_query: Query<(&Interaction, &RelativeCursorPosition, &mut Slider)>) {
+    for (interaction, cursor, mut slider) in sliders_query.iter_mut() {
+        if !matches!(interaction, Interaction::Pressed) {
+            continue;
+        }
+        let Some(cursor) = cursor.normalized else {
+            continue;
+        };
+        let value = cursor.x.clamp(0., 1.);
+        if slider.value != value {
+            slider.value = value;
+        }
+    }
+}
+
+fn update_slider_fills(
+    sliders_query: Query<(&Slider, &Children), Changed<Slider>>,
+    mut fills_query: Query<&mut Style, With<SliderFill>>,
+) {
+    for (slider, children) in sliders_query.iter() {
+        let mut fills = fills_query.iter_many_mut(children.iter());
+        while let Some(mut fill_style) = fills.fetch_next() {
+            fill_style.width = Val::Percent(slider.value * 100.);
+        }
+    }
+}
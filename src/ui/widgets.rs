@@ -1,8 +1,16 @@
 //! Helper traits for creating common widgets.
 
-use bevy::{ecs::system::EntityCommands, prelude::*, ui::Val::*};
+use bevy::{
+    ecs::system::EntityCommands,
+    prelude::*,
+    ui::{RelativeCursorPosition, Val::*},
+};
 
-use super::{interaction::InteractionPalette, palette::*};
+use super::{
+    interaction::InteractionPalette,
+    palette::*,
+    slider::{Slider, SliderFill},
+};
 
 /// An extension trait for spawning UI widgets.
 pub trait Widgets {
@@ -23,6 +31,9 @@ pub trait Widgets {
         marker: C,
         font: Handle<Font>,
     ) -> EntityCommands;
+
+    /// Spawn a slider track starting at `value`, from 0 to 1. The marker is put on the track.
+    fn slider_with_marker<C: Component>(&mut self, value: f32, marker: C) -> EntityCommands;
 }
 
 impl<T: Spawn> Widgets for T {
@@ -31,8 +42,9 @@ impl<T: Spawn> Widgets for T {
             Name::new("Button"),
             ButtonBundle {
                 style: Style {
-                    width: Px(170.0),
+                    min_width: Px(170.0),
                     height: Px(55.0),
+                    padding: UiRect::horizontal(Px(15.0)),
                     justify_content: JustifyContent::Center,
                     align_items: AlignItems::Center,
                     ..default()
@@ -171,6 +183,42 @@ impl<T: Spawn> Widgets for T {
         });
         entity
     }
+
+    fn slider_with_marker<C: Component>(&mut self, value: f32, marker: C) -> EntityCommands {
+        let mut entity = self.spawn((
+            Name::new("Slider"),
+            ButtonBundle {
+                style: Style {
+                    width: Px(250.0),
+                    height: Px(20.0),
+                    ..default()
+                },
+                background_color: BackgroundColor(SLIDER_TRACK_COLOR),
+                border_radius: BorderRadius::all(Val::Px(5.)),
+                ..default()
+            },
+            RelativeCursorPosition::default(),
+            Slider { value },
+            marker,
+        ));
+        entity.with_children(|children| {
+            children.spawn((
+                Name::new("Slider Fill"),
+                NodeBundle {
+                    style: Style {
+                        width: Percent(value * 100.),
+                        height: Percent(100.0),
+                        ..default()
+                    },
+                    background_color: BackgroundColor(NODE_BACKGROUND_COLOR),
+                    border_radius: BorderRadius::all(Val::Px(5.)),
+                    ..default()
+                },
+                SliderFill,
+            ));
+        });
+        entity
+    }
 }
 
 /// An extension trait for spawning UI containers.
@@ -182,6 +230,10 @@ pub trait Containers {
     fn bottom_ui_root(&mut self) -> EntityCommands;
     fn bottom_left_ui_root(&mut self) -> EntityCommands;
     fn top_ui_root(&mut self) -> EntityCommands;
+    fn top_right_ui_root(&mut self) -> EntityCommands;
+    /// Below the top labels, for messages shown over the arena
+    fn overlay_ui_root(&mut self) -> EntityCommands;
+    fn top_left_ui_root(&mut self) -> EntityCommands;
 }
 
 impl Containers for Commands<'_, '_> {
@@ -265,6 +317,65 @@ impl Containers for Commands<'_, '_> {
             },
         ))
     }
+
+    fn top_right_ui_root(&mut self) -> EntityCommands {
+        self.spawn((
+            Name::new("Top right UI Root"),
+            NodeBundle {
+                style: Style {
+                    top: Val::Percent(2.),
+                    right: Val::Percent(2.),
+                    width: Percent(30.0),
+                    justify_content: JustifyContent::Start,
+                    align_items: AlignItems::End,
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Px(10.0),
+                    position_type: PositionType::Absolute,
+                    ..default()
+                },
+                ..default()
+            },
+        ))
+    }
+
+    fn overlay_ui_root(&mut self) -> EntityCommands {
+        self.spawn((
+            Name::new("Overlay UI Root"),
+            NodeBundle {
+                style: Style {
+                    top: Val::Percent(12.),
+                    width: Percent(100.0),
+                    justify_content: JustifyContent::Start,
+                    align_items: AlignItems::Center,
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Px(10.0),
+                    position_type: PositionType::Absolute,
+                    ..default()
+                },
+                ..default()
+            },
+        ))
+    }
+
+    fn top_left_ui_root(&mut self) -> EntityCommands {
+        self.spawn((
+            Name::new("Top left UI Root"),
+            NodeBundle {
+                style: Style {
+                    top: Val::Percent(12.),
+                    left: Val::Percent(2.),
+                    width: Percent(30.0),
+                    justify_content: JustifyContent::Start,
+                    align_items: AlignItems::Start,
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Px(10.0),
+                    position_type: PositionType::Absolute,
+                    ..default()
+                },
+                ..default()
+            },
+        ))
+    }
 }
 
 /// An internal trait for types that can spawn entities.